@@ -0,0 +1,466 @@
+use std::collections::{BinaryHeap, VecDeque};
+
+use crate::node_map::{NodeMap, EdgeMap};
+use crate::path_finder::{PathFindable, NodeIndexable, EdgeIndexable};
+use crate::priority_node::PriorityNode;
+use crate::rng::Rng;
+use crate::weighted_graph::{Node, Edge};
+
+pub struct Centrality;
+
+// Share of shortest paths between pairs of other nodes that pass through every node and edge.
+// In undirected graphs every pair counts once.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Betweenness {
+    pub nodes: NodeMap<f64>,
+    pub edges: EdgeMap<f64>
+}
+
+// Sources of shortest paths counted by betweenness
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BetweennessSources {
+    All,
+
+    // Estimate from paths starting at `count` random nodes, scaled up to all of them
+    Sample { count: usize, seed: u64 }
+}
+
+// Scores found by power iteration, with how far the iteration got
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpectralScores {
+    pub scores: NodeMap<f64>,
+    pub iterations: usize,
+
+    // Total change of scores in the last iteration
+    pub residual: f64
+}
+
+// Hub and authority scores of HITS, each of them sums up to 1
+#[derive(Debug, Clone, PartialEq)]
+pub struct HitsScores {
+    // High for nodes with edges to good authorities
+    pub hubs: NodeMap<f64>,
+
+    // High for nodes with edges from good hubs
+    pub authorities: NodeMap<f64>,
+    pub iterations: usize,
+
+    // Total change of hub scores in the last iteration
+    pub residual: f64
+}
+
+// Shortest paths from one source: nodes in order of their distance, shortest path counts
+// and the edges every node can be reached through on a shortest path
+struct ShortestPathDag {
+    order: Vec<usize>,
+    path_count: Vec<f64>,
+    predecessors: Vec<Vec<(usize, Edge)>>
+}
+
+impl Centrality {
+
+    ///
+    /// Ranks nodes with PageRank using power iteration. Edge weights give the probability of
+    /// following every edge out of a node. A node without outgoing weight (dangling) jumps to
+    /// any node, so do random jumps taken with probability 1 - damping. Scores sum up to 1.
+    /// Stops when the scores change by less than tolerance in total, or after max_iter iterations.
+    /// O((V + E) * iterations)
+    ///
+    /// # Panics
+    /// Panics if damping is not within [0, 1] or if any weight is negative.
+    ///
+    pub fn pagerank<'a, W, G>(graph: &'a G, damping: f64, tolerance: f64, max_iter: usize) -> NodeMap<f64>
+        where G: PathFindable<'a, Node, W> + NodeIndexable<Node>, W: Copy + num_traits::ToPrimitive {
+
+        let jumps = vec![1.0 / graph.node_count() as f64; graph.node_count()];
+        Centrality::power_iteration(graph, damping, tolerance, max_iter, jumps)
+    }
+
+    ///
+    /// PageRank where random jumps and jumps from dangling nodes only lead to the given nodes,
+    /// with probabilities proportional to their values. Nodes that are not listed get 0.
+    /// O((V + E) * iterations)
+    ///
+    /// # Panics
+    /// Panics if damping is not within [0, 1], if any weight or personalisation value is negative
+    /// or if personalisation values sum up to 0.
+    ///
+    pub fn personalized_pagerank<'a, W, G>(graph: &'a G, damping: f64, tolerance: f64, max_iter: usize, personalization: &[(Node, f64)]) -> NodeMap<f64>
+        where G: PathFindable<'a, Node, W> + NodeIndexable<Node>, W: Copy + num_traits::ToPrimitive {
+
+        let mut jumps = vec![0.0; graph.node_count()];
+        for (node, value) in personalization {
+            assert!(*value >= 0.0, "Personalisation values can't be negative");
+            jumps[graph.index_of(*node)] += value;
+        }
+        let total: f64 = jumps.iter().sum();
+        assert!(total > 0.0, "Personalisation values sum up to 0");
+        for jump in jumps.iter_mut() {
+            *jump /= total;
+        }
+
+        Centrality::power_iteration(graph, damping, tolerance, max_iter, jumps)
+    }
+
+    ///
+    /// Betweenness centrality of nodes and edges with Brandes' algorithm, every edge counts as 1.
+    /// Self-loops are ignored and parallel edges are separate paths.
+    /// O(V E), or O(k E) when sampling k sources
+    ///
+    /// # Panics
+    /// Panics if the sample is empty or larger than the graph.
+    ///
+    pub fn betweenness<'a, Dist, G>(graph: &'a G, sources: BetweennessSources) -> Betweenness
+        where G: EdgeIndexable<'a, Node, Edge, Dist> + NodeIndexable<Node> {
+
+        Centrality::brandes(graph, sources, |source| {
+            let mut distance: Vec<Option<usize>> = vec![None; graph.node_count()];
+            let mut dag = ShortestPathDag::new(graph.node_count(), source);
+            let mut queue = VecDeque::from([source]);
+            distance[source] = Some(0);
+
+            while let Some(index) = queue.pop_front() {
+                dag.order.push(index);
+                let next_distance = distance[index].map(|distance| distance + 1);
+                for (edge, neighbour, _) in graph.get_edges(graph.node_at(index)) {
+                    let neighbour = graph.index_of(neighbour);
+                    if distance[neighbour].is_none() {
+                        distance[neighbour] = next_distance;
+                        queue.push_back(neighbour);
+                    }
+                    if distance[neighbour] == next_distance {
+                        dag.add_predecessor(neighbour, index, edge);
+                    }
+                }
+            }
+            dag
+        })
+    }
+
+    ///
+    /// Betweenness centrality of nodes and edges with Brandes' algorithm, using Dijkstra
+    /// to find shortest paths by weight. Parallel edges are separate paths.
+    /// O(V E log V), or O(k E log V) when sampling k sources
+    ///
+    /// # Panics
+    /// Panics if the sample is empty or larger than the graph, or if any weight is not positive.
+    ///
+    pub fn weighted_betweenness<'a, W, G>(graph: &'a G, sources: BetweennessSources) -> Betweenness
+        where G: EdgeIndexable<'a, Node, Edge, W> + NodeIndexable<Node>, W: Copy + Ord + num_traits::Zero {
+
+        Centrality::brandes(graph, sources, |source| {
+            let mut distance: Vec<Option<W>> = vec![None; graph.node_count()];
+            let mut settled = vec![false; graph.node_count()];
+            let mut dag = ShortestPathDag::new(graph.node_count(), source);
+            let mut queue = BinaryHeap::from([PriorityNode { priority: W::zero(), node: source }]);
+            distance[source] = Some(W::zero());
+
+            while let Some(PriorityNode { priority: curr_distance, node: index }) = queue.pop() {
+                // Duplicates happen, since nodes are added again instead of decreasing their priority
+                if settled[index] {
+                    continue;
+                }
+                settled[index] = true;
+                dag.order.push(index);
+
+                for (edge, neighbour, weight) in graph.get_edges(graph.node_at(index)) {
+                    assert!(weight > W::zero(), "Weights have to be positive");
+                    let neighbour = graph.index_of(neighbour);
+                    let through_curr = curr_distance + weight;
+                    if distance[neighbour].is_none_or(|distance| through_curr < distance) {
+                        distance[neighbour] = Some(through_curr);
+                        dag.predecessors[neighbour].clear();
+                        dag.path_count[neighbour] = 0.0;
+                        queue.push(PriorityNode { priority: through_curr, node: neighbour });
+                    }
+                    if distance[neighbour] == Some(through_curr) {
+                        dag.add_predecessor(neighbour, index, edge);
+                    }
+                }
+            }
+            dag
+        })
+    }
+
+    ///
+    /// Closeness centrality: the number of nodes reachable from the node divided by the sum
+    /// of distances to them, every edge counting as 1. Scaled by the share of other nodes that
+    /// are reachable, so nodes of small components don't get high scores. Nodes that reach
+    /// nothing get 0.
+    /// O(V E)
+    ///
+    pub fn closeness<'a, Dist, G>(graph: &'a G) -> NodeMap<f64>
+        where G: PathFindable<'a, Node, Dist> + NodeIndexable<Node> {
+
+        let other_count = graph.node_count().saturating_sub(1) as f64;
+        Centrality::from_distances(graph, |distances| {
+            let reached = distances.iter().filter(|distance| **distance > 0).count() as f64;
+            let total: usize = distances.iter().sum();
+            if total == 0 { 0.0 } else { reached / total as f64 * reached / other_count }
+        })
+    }
+
+    ///
+    /// Harmonic centrality: the sum of inverted distances to all other nodes, every edge counting
+    /// as 1. Unreachable nodes add 0, so it works for disconnected graphs without scaling.
+    /// O(V E)
+    ///
+    pub fn harmonic<'a, Dist, G>(graph: &'a G) -> NodeMap<f64>
+        where G: PathFindable<'a, Node, Dist> + NodeIndexable<Node> {
+
+        Centrality::from_distances(graph, |distances| {
+            distances.iter().filter(|distance| **distance > 0).map(|distance| 1.0 / *distance as f64).sum()
+        })
+    }
+
+    // Scores every node from BFS distances to the nodes it reaches, unreachable nodes have distance 0
+    fn from_distances<'a, Dist, G>(graph: &'a G, score: impl Fn(&[usize]) -> f64) -> NodeMap<f64>
+        where G: PathFindable<'a, Node, Dist> + NodeIndexable<Node> {
+
+        let mut distances = vec![0; graph.node_count()];
+        let mut reached = vec![false; graph.node_count()];
+        let mut queue = VecDeque::new();
+        let scores = (0..graph.node_count())
+            .map(|source| {
+                distances.fill(0);
+                reached.fill(false);
+                reached[source] = true;
+                queue.push_back(source);
+                while let Some(index) = queue.pop_front() {
+                    for (neighbour, _) in graph.get_neighbours(graph.node_at(index)) {
+                        let neighbour = graph.index_of(neighbour);
+                        if !reached[neighbour] {
+                            reached[neighbour] = true;
+                            distances[neighbour] = distances[index] + 1;
+                            queue.push_back(neighbour);
+                        }
+                    }
+                }
+                score(&distances)
+            })
+            .collect();
+        NodeMap::from_vec(scores)
+    }
+
+    // Brandes' accumulation: walks shortest path DAGs from every source backwards, handing
+    // every node's dependency to its predecessors in proportion to their path counts
+    fn brandes<'a, Dist, G>(graph: &'a G, sources: BetweennessSources, mut shortest_paths: impl FnMut(usize) -> ShortestPathDag) -> Betweenness
+        where G: EdgeIndexable<'a, Node, Edge, Dist> + NodeIndexable<Node> {
+
+        let node_count = graph.node_count();
+        let mut source_nodes = (0..node_count).collect::<Vec<usize>>();
+        let mut scale = if graph.is_directed() { 1.0 } else { 0.5 };
+        if let BetweennessSources::Sample { count, seed } = sources {
+            assert!(count > 0 && count <= node_count, "Sample has to be within 1..=node_count");
+            Rng::new(seed).shuffle(&mut source_nodes);
+            source_nodes.truncate(count);
+            scale *= node_count as f64 / count as f64;
+        }
+
+        let mut node_scores = vec![0.0; node_count];
+        let mut edge_scores = vec![0.0; graph.edge_count()];
+        let mut dependency = vec![0.0; node_count];
+        for source in source_nodes {
+            let dag = shortest_paths(source);
+            for index in dag.order.iter() {
+                dependency[*index] = 0.0;
+            }
+            for index in dag.order.iter().rev() {
+                for (predecessor, edge) in dag.predecessors[*index].iter() {
+                    let share = dag.path_count[*predecessor] / dag.path_count[*index] * (1.0 + dependency[*index]);
+                    edge_scores[graph.edge_index(*edge)] += share;
+                    dependency[*predecessor] += share;
+                }
+                if *index != source {
+                    node_scores[*index] += dependency[*index];
+                }
+            }
+        }
+
+        for score in node_scores.iter_mut().chain(edge_scores.iter_mut()) {
+            *score *= scale;
+        }
+        Betweenness { nodes: NodeMap::from_vec(node_scores), edges: EdgeMap::from_vec(edge_scores) }
+    }
+
+    ///
+    /// Eigenvector centrality: a node is important if important nodes have edges to it.
+    /// Scores are the principal eigenvector of the weighted adjacency matrix, found by power
+    /// iteration and scaled to unit length. Iterates on A + I, which has the same eigenvectors,
+    /// so that bipartite graphs converge as well.
+    /// Stops when the scores change by less than tolerance in total, or after max_iter iterations.
+    /// O((V + E) * iterations)
+    ///
+    /// # Panics
+    /// Panics if any weight is negative.
+    ///
+    pub fn eigenvector<'a, W, G>(graph: &'a G, tolerance: f64, max_iter: usize) -> SpectralScores
+        where G: PathFindable<'a, Node, W> + NodeIndexable<Node>, W: Copy + num_traits::ToPrimitive {
+
+        let start = vec![1.0 / graph.node_count() as f64; graph.node_count()];
+        Centrality::iterate(start, tolerance, max_iter, |scores| {
+            let mut next_scores = scores.to_vec();
+            Centrality::push_along_edges(graph, scores, &mut next_scores);
+            Centrality::normalize(&mut next_scores, Centrality::length);
+            next_scores
+        })
+    }
+
+    ///
+    /// Katz centrality: every node gets beta, plus alpha times the scores of nodes with edges to it,
+    /// so paths of every length count with weight decreasing as alpha to their length.
+    /// Scores are scaled to unit length. Converges if alpha is below the inverse of the largest
+    /// eigenvalue of the adjacency matrix.
+    /// Stops when the scores change by less than tolerance in total, or after max_iter iterations.
+    /// O((V + E) * iterations)
+    ///
+    /// # Panics
+    /// Panics if any weight is negative.
+    ///
+    pub fn katz<'a, W, G>(graph: &'a G, alpha: f64, beta: f64, tolerance: f64, max_iter: usize) -> SpectralScores
+        where G: PathFindable<'a, Node, W> + NodeIndexable<Node>, W: Copy + num_traits::ToPrimitive {
+
+        let mut result = Centrality::iterate(vec![0.0; graph.node_count()], tolerance, max_iter, |scores| {
+            let mut spread = vec![0.0; scores.len()];
+            Centrality::push_along_edges(graph, scores, &mut spread);
+            spread.iter().map(|score| alpha * score + beta).collect()
+        });
+
+        let mut scores = result.scores.into_vec();
+        Centrality::normalize(&mut scores, Centrality::length);
+        result.scores = NodeMap::from_vec(scores);
+        result
+    }
+
+    ///
+    /// Hub and authority scores of HITS: a good hub has edges to good authorities and a good
+    /// authority has edges from good hubs. In undirected graphs both scores are the same.
+    /// Stops when hub scores change by less than tolerance in total, or after max_iter iterations.
+    /// O((V + E) * iterations)
+    ///
+    /// # Panics
+    /// Panics if any weight is negative.
+    ///
+    pub fn hits<'a, W, G>(graph: &'a G, tolerance: f64, max_iter: usize) -> HitsScores
+        where G: PathFindable<'a, Node, W> + NodeIndexable<Node>, W: Copy + num_traits::ToPrimitive {
+
+        let node_count = graph.node_count();
+        let mut authorities = vec![0.0; node_count];
+        let result = Centrality::iterate(vec![1.0 / node_count as f64; node_count], tolerance, max_iter, |hubs| {
+            authorities.fill(0.0);
+            Centrality::push_along_edges(graph, hubs, &mut authorities);
+            Centrality::normalize(&mut authorities, |scores| scores.iter().sum());
+
+            // Hubs collect authority scores of their neighbours
+            let mut next_hubs = (0..node_count)
+                .map(|index| graph.get_neighbours(graph.node_at(index))
+                    .map(|(neighbour, weight)| Centrality::weight_of(weight) * authorities[graph.index_of(neighbour)])
+                    .sum())
+                .collect::<Vec<f64>>();
+            Centrality::normalize(&mut next_hubs, |scores| scores.iter().sum());
+            next_hubs
+        });
+
+        HitsScores { hubs: result.scores, authorities: NodeMap::from_vec(authorities), iterations: result.iterations, residual: result.residual }
+    }
+
+    // Power iteration from the start scores until the total change drops below tolerance
+    fn iterate(start: Vec<f64>, tolerance: f64, max_iter: usize, mut step: impl FnMut(&[f64]) -> Vec<f64>) -> SpectralScores {
+        let mut scores = start;
+        let mut iterations = 0;
+        let mut residual = f64::INFINITY;
+        while iterations < max_iter && residual >= tolerance {
+            let next_scores = step(&scores);
+            residual = next_scores.iter().zip(scores.iter()).map(|(next, score)| (next - score).abs()).sum();
+            scores = next_scores;
+            iterations += 1;
+        }
+        SpectralScores { scores: NodeMap::from_vec(scores), iterations, residual }
+    }
+
+    // Adds the weighted score of every node to the nodes its edges lead to
+    fn push_along_edges<'a, W, G>(graph: &'a G, scores: &[f64], next_scores: &mut [f64])
+        where G: PathFindable<'a, Node, W> + NodeIndexable<Node>, W: Copy + num_traits::ToPrimitive {
+
+        for (index, score) in scores.iter().enumerate() {
+            for (neighbour, weight) in graph.get_neighbours(graph.node_at(index)) {
+                next_scores[graph.index_of(neighbour)] += Centrality::weight_of(weight) * score;
+            }
+        }
+    }
+
+    fn length(scores: &[f64]) -> f64 {
+        scores.iter().map(|score| score * score).sum::<f64>().sqrt()
+    }
+
+    // Divides scores by their norm, unless all of them are 0
+    fn normalize(scores: &mut [f64], norm: impl Fn(&[f64]) -> f64) {
+        let norm = norm(scores);
+        if norm > 0.0 {
+            for score in scores.iter_mut() {
+                *score /= norm;
+            }
+        }
+    }
+
+    fn weight_of<W: num_traits::ToPrimitive>(weight: W) -> f64 {
+        let weight = weight.to_f64().expect("Weight can't be converted to f64");
+        assert!(weight >= 0.0, "Weights can't be negative");
+        weight
+    }
+
+    // Pushes the score of every node along its outgoing edges, so no incoming edges are needed
+    fn power_iteration<'a, W, G>(graph: &'a G, damping: f64, tolerance: f64, max_iter: usize, jumps: Vec<f64>) -> NodeMap<f64>
+        where G: PathFindable<'a, Node, W> + NodeIndexable<Node>, W: Copy + num_traits::ToPrimitive {
+
+        assert!((0.0..=1.0).contains(&damping), "Damping has to be within [0, 1]");
+
+        let node_count = graph.node_count();
+        let weight_of = Centrality::weight_of::<W>;
+
+        let out_weight = (0..node_count)
+            .map(|index| graph.get_neighbours(graph.node_at(index)).map(|(_, weight)| weight_of(weight)).sum::<f64>())
+            .collect::<Vec<f64>>();
+
+        let mut scores = vec![1.0 / node_count as f64; node_count];
+        for _ in 0..max_iter {
+            let mut next_scores = vec![0.0; node_count];
+            let mut dangling = 0.0;
+            for (index, score) in scores.iter().enumerate() {
+                if out_weight[index] == 0.0 {
+                    dangling += score;
+                    continue;
+                }
+                for (neighbour, weight) in graph.get_neighbours(graph.node_at(index)) {
+                    next_scores[graph.index_of(neighbour)] += damping * score * weight_of(weight) / out_weight[index];
+                }
+            }
+
+            let jumping = damping * dangling + 1.0 - damping;
+            for (next_score, jump) in next_scores.iter_mut().zip(jumps.iter()) {
+                *next_score += jumping * jump;
+            }
+
+            let change: f64 = next_scores.iter().zip(scores.iter()).map(|(next, score)| (next - score).abs()).sum();
+            scores = next_scores;
+            if change < tolerance {
+                break;
+            }
+        }
+
+        NodeMap::from_vec(scores)
+    }
+}
+
+impl ShortestPathDag {
+    fn new(node_count: usize, source: usize) -> Self {
+        let mut path_count = vec![0.0; node_count];
+        path_count[source] = 1.0;
+        ShortestPathDag { order: Vec::new(), path_count, predecessors: vec![Vec::new(); node_count] }
+    }
+
+    fn add_predecessor(&mut self, index: usize, predecessor: usize, edge: Edge) {
+        self.path_count[index] += self.path_count[predecessor];
+        self.predecessors[index].push((predecessor, edge));
+    }
+}
@@ -0,0 +1,260 @@
+use std::time::{Duration, Instant};
+
+use crate::clustering::Clustering;
+use crate::cores::Cores;
+use crate::path_finder::{NodeIndexable, EdgeIndexable};
+use crate::weighted_graph::{Node, Edge};
+
+pub struct Cliques;
+
+// Largest clique found by `maximum_clique`, nodes are ordered by node index
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MaximumClique {
+    pub nodes: Vec<Node>,
+
+    // False if the time budget ran out before the search could prove no clique is larger
+    pub is_optimal: bool
+}
+
+// Lazy iterator over maximal cliques, created by `Cliques::maximal_cliques`.
+// Every clique is ordered by node index.
+pub struct MaximalCliques {
+    nodes: Vec<Node>,
+    neighbours: Vec<Vec<usize>>,
+    ordering: Vec<usize>,
+
+    // Position of every node in the degeneracy ordering
+    positions: Vec<usize>,
+    next_root: usize,
+
+    // Nodes of the clique being grown, every frame of the stack added one of them
+    clique: Vec<usize>,
+    stack: Vec<BronKerboschFrame>
+}
+
+// Candidates that can still extend the clique (P), nodes that already did (X)
+// and the candidates left to branch on, those not adjacent to the pivot
+struct BronKerboschFrame {
+    candidates: Vec<usize>,
+    excluded: Vec<usize>,
+    branches: Vec<usize>,
+    next: usize
+}
+
+// State of the branch and bound search for a maximum clique
+struct MaximumCliqueSearch<'a> {
+    neighbours: &'a [Vec<usize>],
+    best: Vec<usize>,
+    deadline: Option<Instant>,
+    timed_out: bool
+}
+
+impl Cliques {
+
+    ///
+    /// Lazily lists every maximal clique of an undirected graph using Bron–Kerbosch with pivoting,
+    /// starting from nodes in degeneracy ordering (Eppstein, Löffler and Strash).
+    /// Self-loops and parallel edges are ignored, a node without neighbours is a clique of its own.
+    /// O(d * V * 3^(d/3)) for degeneracy d
+    ///
+    /// # Panics
+    /// Panics if the graph is directed.
+    ///
+    pub fn maximal_cliques<'a, W, G>(graph: &'a G) -> MaximalCliques
+        where G: EdgeIndexable<'a, Node, Edge, W> + NodeIndexable<Node> {
+
+        assert!(!graph.is_directed(), "Cliques are only defined for undirected graphs");
+
+        let ordering: Vec<usize> = Cores::core_numbers(graph).ordering.into_iter()
+            .map(|node| graph.index_of(node))
+            .collect();
+        let mut positions = vec![0; ordering.len()];
+        for (position, &index) in ordering.iter().enumerate() {
+            positions[index] = position;
+        }
+
+        MaximalCliques {
+            nodes: (0..graph.node_count()).map(|index| graph.node_at(index)).collect(),
+            neighbours: Clustering::neighbours(graph),
+            ordering,
+            positions,
+            next_root: 0,
+            clique: Vec::new(),
+            stack: Vec::new()
+        }
+    }
+
+    ///
+    /// Finds a largest clique of an undirected graph with branch and bound, where greedy colouring
+    /// of candidates bounds the size of cliques they can form (Tomita and Seki).
+    /// Stops early with the largest clique found so far when the time budget runs out.
+    /// O(2^V) in the worst case
+    ///
+    /// # Panics
+    /// Panics if the graph is directed.
+    ///
+    pub fn maximum_clique<'a, W, G>(graph: &'a G, budget: Option<Duration>) -> MaximumClique
+        where G: EdgeIndexable<'a, Node, Edge, W> + NodeIndexable<Node> {
+
+        assert!(!graph.is_directed(), "Cliques are only defined for undirected graphs");
+
+        let neighbours = Clustering::neighbours(graph);
+        let mut search = MaximumCliqueSearch {
+            neighbours: &neighbours,
+            best: Vec::new(),
+            deadline: budget.map(|budget| Instant::now() + budget),
+            timed_out: false
+        };
+
+        // Nodes of high degree come last, so they are tried first
+        let mut candidates: Vec<usize> = (0..neighbours.len()).collect();
+        candidates.sort_by_key(|&index| neighbours[index].len());
+        search.expand(&mut Vec::new(), candidates);
+
+        let mut best = search.best;
+        best.sort_unstable();
+        MaximumClique {
+            nodes: best.into_iter().map(|index| graph.node_at(index)).collect(),
+            is_optimal: !search.timed_out
+        }
+    }
+
+    // Elements of sorted `set` that are also in sorted `other`
+    fn intersect(set: &[usize], other: &[usize]) -> Vec<usize> {
+        let mut common = Vec::new();
+        let mut other = other.iter().peekable();
+        for &element in set {
+            while other.next_if(|&&next| next < element).is_some() {}
+            if other.peek() == Some(&&element) {
+                common.push(element);
+            }
+        }
+        common
+    }
+}
+
+impl MaximalCliques {
+    fn push_frame(&mut self, candidates: Vec<usize>, excluded: Vec<usize>) {
+        // Pivot with the most neighbours among candidates, they don't need to be branched on
+        let common = |node: usize| Cliques::intersect(&candidates, &self.neighbours[node]).len();
+        let pivot = candidates.iter().chain(excluded.iter()).copied()
+            .max_by_key(|&node| common(node))
+            .expect("Frames are never empty");
+        let branches = candidates.iter().copied()
+            .filter(|node| self.neighbours[pivot].binary_search(node).is_err())
+            .collect();
+
+        self.stack.push(BronKerboschFrame { candidates, excluded, branches, next: 0 });
+    }
+
+    // Adds the node to the clique, returns the clique if it became maximal
+    fn grow(&mut self, node: usize, candidates: Vec<usize>, excluded: Vec<usize>) -> Option<Vec<Node>> {
+        self.clique.push(node);
+        if !candidates.is_empty() {
+            self.push_frame(candidates, excluded);
+            return None;
+        }
+
+        let clique = excluded.is_empty().then(|| {
+            let mut clique = self.clique.clone();
+            clique.sort_unstable();
+            clique.into_iter().map(|index| self.nodes[index]).collect()
+        });
+        self.clique.pop();
+        clique
+    }
+}
+
+impl Iterator for MaximalCliques {
+    type Item = Vec<Node>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let Some(frame) = self.stack.last_mut() else {
+                // Cliques whose earliest node in the ordering is the root
+                let root = *self.ordering.get(self.next_root)?;
+                self.next_root += 1;
+                let (later, earlier): (Vec<usize>, Vec<usize>) = self.neighbours[root].iter()
+                    .partition(|&&neighbour| self.positions[neighbour] > self.positions[root]);
+                if let Some(clique) = self.grow(root, later, earlier) {
+                    return Some(clique);
+                }
+                continue;
+            };
+
+            let Some(&node) = frame.branches.get(frame.next) else {
+                self.stack.pop();
+                self.clique.pop();
+                continue;
+            };
+            frame.next += 1;
+
+            let candidates = Cliques::intersect(&frame.candidates, &self.neighbours[node]);
+            let excluded = Cliques::intersect(&frame.excluded, &self.neighbours[node]);
+            let position = frame.candidates.binary_search(&node).expect("Branches are candidates");
+            frame.candidates.remove(position);
+            let position = frame.excluded.binary_search(&node).unwrap_err();
+            frame.excluded.insert(position, node);
+
+            if let Some(clique) = self.grow(node, candidates, excluded) {
+                return Some(clique);
+            }
+        }
+    }
+}
+
+impl MaximumCliqueSearch<'_> {
+    // Tries to extend the clique with every candidate, from the one with the highest colour
+    fn expand(&mut self, clique: &mut Vec<usize>, candidates: Vec<usize>) {
+        if self.deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            self.timed_out = true;
+            return;
+        }
+
+        let (order, colours) = self.colour(candidates);
+        for position in (0..order.len()).rev() {
+            // No more than `colours[position]` of the remaining candidates can form a clique
+            if self.timed_out || clique.len() + colours[position] <= self.best.len() {
+                return;
+            }
+
+            let node = order[position];
+            let remaining: Vec<usize> = order[..position].iter().copied()
+                .filter(|candidate| self.neighbours[node].binary_search(candidate).is_ok())
+                .collect();
+
+            clique.push(node);
+            if remaining.is_empty() {
+                if clique.len() > self.best.len() {
+                    self.best = clique.clone();
+                }
+            } else {
+                self.expand(clique, remaining);
+            }
+            clique.pop();
+        }
+    }
+
+    // Greedily colours candidates so that neighbours differ, returns them ordered by colour
+    // with the colour of every one of them, counted from 1
+    fn colour(&self, candidates: Vec<usize>) -> (Vec<usize>, Vec<usize>) {
+        let mut classes: Vec<Vec<usize>> = Vec::new();
+        for candidate in candidates {
+            let free = classes.iter().position(|class| {
+                class.iter().all(|member| self.neighbours[candidate].binary_search(member).is_err())
+            });
+            match free {
+                Some(class) => classes[class].push(candidate),
+                None => classes.push(vec![candidate])
+            }
+        }
+
+        let mut order = Vec::new();
+        let mut colours = Vec::new();
+        for (colour, class) in classes.into_iter().enumerate() {
+            colours.extend(std::iter::repeat_n(colour + 1, class.len()));
+            order.extend(class);
+        }
+        (order, colours)
+    }
+}
@@ -0,0 +1,143 @@
+use crate::node_map::NodeMap;
+use crate::path_finder::{NodeIndexable, EdgeIndexable};
+use crate::weighted_graph::{Node, Edge};
+
+pub struct Clustering;
+
+// Triangles every node is part of and all triangles of the graph
+#[derive(Debug, Clone, PartialEq)]
+pub struct Triangles {
+    pub nodes: NodeMap<usize>,
+    pub total: usize
+}
+
+impl Clustering {
+
+    ///
+    /// Counts triangles of an undirected graph, visiting every one of them once from its node
+    /// of the lowest degree. Self-loops and parallel edges are ignored.
+    /// O(E * sqrt(E))
+    ///
+    /// # Panics
+    /// Panics if the graph is directed.
+    ///
+    pub fn triangles<'a, W, G>(graph: &'a G) -> Triangles
+        where G: EdgeIndexable<'a, Node, Edge, W> + NodeIndexable<Node> {
+
+        Clustering::count_triangles(&Clustering::neighbours(graph))
+    }
+
+    ///
+    /// Local clustering coefficient of every node of an undirected graph: the share of pairs
+    /// of its neighbours that are connected. Nodes with less than two neighbours have 0.
+    /// O(E * sqrt(E))
+    ///
+    /// # Panics
+    /// Panics if the graph is directed.
+    ///
+    pub fn local<'a, W, G>(graph: &'a G) -> NodeMap<f64>
+        where G: EdgeIndexable<'a, Node, Edge, W> + NodeIndexable<Node> {
+
+        let neighbours = Clustering::neighbours(graph);
+        let triangles = Clustering::count_triangles(&neighbours);
+        let coefficients = triangles.nodes.values().iter().zip(neighbours.iter())
+            .map(|(&count, list)| match Clustering::pairs(list.len()) {
+                0 => 0.0,
+                pairs => count as f64 / pairs as f64
+            })
+            .collect();
+        NodeMap::from_vec(coefficients)
+    }
+
+    ///
+    /// Mean of local clustering coefficients over all nodes of an undirected graph, 0 for an empty graph.
+    /// O(E * sqrt(E))
+    ///
+    /// # Panics
+    /// Panics if the graph is directed.
+    ///
+    pub fn average<'a, W, G>(graph: &'a G) -> f64
+        where G: EdgeIndexable<'a, Node, Edge, W> + NodeIndexable<Node> {
+
+        let coefficients = Clustering::local(graph);
+        if coefficients.is_empty() {
+            return 0.0;
+        }
+        coefficients.values().iter().sum::<f64>() / coefficients.len() as f64
+    }
+
+    ///
+    /// Transitivity (global clustering coefficient) of an undirected graph: three times
+    /// the number of triangles divided by the number of paths of length two, 0 if there are none.
+    /// O(E * sqrt(E))
+    ///
+    /// # Panics
+    /// Panics if the graph is directed.
+    ///
+    pub fn transitivity<'a, W, G>(graph: &'a G) -> f64
+        where G: EdgeIndexable<'a, Node, Edge, W> + NodeIndexable<Node> {
+
+        let neighbours = Clustering::neighbours(graph);
+        let paths: usize = neighbours.iter().map(|list| Clustering::pairs(list.len())).sum();
+        match paths {
+            0 => 0.0,
+            paths => 3.0 * Clustering::count_triangles(&neighbours).total as f64 / paths as f64
+        }
+    }
+
+    // Triangles of the graph with the given neighbour lists
+    fn count_triangles(neighbours: &[Vec<usize>]) -> Triangles {
+        // Rank nodes by degree and orient every edge towards the higher rank,
+        // then no node has more than sqrt(2E) edges out
+        let rank_of = |index: usize| (neighbours[index].len(), index);
+        let higher: Vec<Vec<usize>> = neighbours.iter().enumerate()
+            .map(|(index, list)| list.iter().copied().filter(|&neighbour| rank_of(neighbour) > rank_of(index)).collect())
+            .collect();
+
+        let mut counts = vec![0; neighbours.len()];
+        let mut total = 0;
+        let mut marked = vec![false; neighbours.len()];
+        for first in 0..neighbours.len() {
+            for &second in higher[first].iter() {
+                marked[second] = true;
+            }
+            for &second in higher[first].iter() {
+                for &third in higher[second].iter() {
+                    if marked[third] {
+                        counts[first] += 1;
+                        counts[second] += 1;
+                        counts[third] += 1;
+                        total += 1;
+                    }
+                }
+            }
+            for &second in higher[first].iter() {
+                marked[second] = false;
+            }
+        }
+
+        Triangles { nodes: NodeMap::from_vec(counts), total }
+    }
+
+    // Pairs of neighbours of a node, the paths of length two through it
+    fn pairs(degree: usize) -> usize {
+        degree * degree.saturating_sub(1) / 2
+    }
+
+    // Sorted indices of distinct neighbours of every node of an undirected graph, without the node itself
+    pub(crate) fn neighbours<'a, W, G>(graph: &'a G) -> Vec<Vec<usize>>
+        where G: EdgeIndexable<'a, Node, Edge, W> + NodeIndexable<Node> {
+
+        assert!(!graph.is_directed(), "Clustering is only defined for undirected graphs");
+
+        (0..graph.node_count()).map(|index| {
+            let mut list: Vec<usize> = graph.get_edges(graph.node_at(index))
+                .map(|(_, neighbour, _)| graph.index_of(neighbour))
+                .filter(|&neighbour| neighbour != index)
+                .collect();
+            list.sort_unstable();
+            list.dedup();
+            list
+        }).collect()
+    }
+}
@@ -0,0 +1,192 @@
+use std::cmp::Reverse;
+use std::collections::{BTreeSet, HashSet};
+
+use crate::clustering::Clustering;
+use crate::cores::Cores;
+use crate::node_map::NodeMap;
+use crate::path_finder::{NodeIndexable, EdgeIndexable};
+use crate::weighted_graph::{Node, Edge};
+
+pub struct Colouring;
+
+// Colour of every node, numbered 0..count, neighbours never share a colour
+#[derive(Debug, Clone, PartialEq)]
+pub struct VertexColouring {
+    pub colours: NodeMap<usize>,
+    pub count: usize
+}
+
+// Order in which greedy colouring visits nodes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColouringOrder {
+    // Nodes of the highest degree first
+    LargestFirst,
+
+    // Reverse of the order of repeatedly removing a node of the lowest degree,
+    // uses at most degeneracy + 1 colours
+    SmallestLast,
+
+    // Next is the node with the most distinct colours among its neighbours,
+    // ties go to the highest degree (Brélaz)
+    DSatur
+}
+
+// State of the backtracking search for a colouring with a limited number of colours
+struct ColouringSearch<'a> {
+    neighbours: &'a [Vec<usize>],
+    colour_limit: usize,
+    colours: Vec<Option<usize>>,
+
+    // How many neighbours of every node have every colour
+    conflicts: Vec<Vec<usize>>
+}
+
+impl Colouring {
+
+    ///
+    /// Colours nodes of an undirected graph one by one in the given order,
+    /// giving each of them the lowest colour none of its neighbours has.
+    /// Self-loops and parallel edges are ignored.
+    /// O((V + E) log V)
+    ///
+    /// # Panics
+    /// Panics if the graph is directed.
+    ///
+    pub fn greedy<'a, W, G>(graph: &'a G, order: ColouringOrder) -> VertexColouring
+        where G: EdgeIndexable<'a, Node, Edge, W> + NodeIndexable<Node> {
+
+        assert!(!graph.is_directed(), "Colouring is only defined for undirected graphs");
+
+        let neighbours = Clustering::neighbours(graph);
+        let ordering: Vec<usize> = match order {
+            ColouringOrder::LargestFirst => {
+                let mut ordering: Vec<usize> = (0..neighbours.len()).collect();
+                ordering.sort_by_key(|&index| Reverse(neighbours[index].len()));
+                ordering
+            },
+            ColouringOrder::SmallestLast => Cores::core_numbers(graph).ordering.into_iter()
+                .rev()
+                .map(|node| graph.index_of(node))
+                .collect(),
+            ColouringOrder::DSatur => return Colouring::dsatur(&neighbours)
+        };
+
+        let mut colours = vec![None; neighbours.len()];
+        let mut taken = Vec::new();
+        for index in ordering {
+            colours[index] = Some(Colouring::lowest_free(&neighbours[index], &colours, &mut taken));
+        }
+        Colouring::finish(colours)
+    }
+
+    ///
+    /// Finds a colouring of an undirected graph with at most k colours by backtracking,
+    /// or None if there is none. Nodes are picked as in DSatur and a new colour is only tried once.
+    /// Self-loops and parallel edges are ignored. Meant for small graphs.
+    /// O(k^V) in the worst case
+    ///
+    /// # Panics
+    /// Panics if the graph is directed.
+    ///
+    pub fn k_colouring<'a, W, G>(graph: &'a G, k: usize) -> Option<VertexColouring>
+        where G: EdgeIndexable<'a, Node, Edge, W> + NodeIndexable<Node> {
+
+        assert!(!graph.is_directed(), "Colouring is only defined for undirected graphs");
+
+        let neighbours = Clustering::neighbours(graph);
+        let mut search = ColouringSearch {
+            neighbours: &neighbours,
+            colour_limit: k,
+            colours: vec![None; neighbours.len()],
+            conflicts: vec![vec![0; k]; neighbours.len()]
+        };
+
+        search.extend(0, 0).then(|| Colouring::finish(search.colours))
+    }
+
+    fn dsatur(neighbours: &[Vec<usize>]) -> VertexColouring {
+        let mut colours = vec![None; neighbours.len()];
+        let mut neighbour_colours: Vec<HashSet<usize>> = vec![HashSet::new(); neighbours.len()];
+        let key = |index: usize, saturation: usize| (saturation, neighbours[index].len(), Reverse(index));
+
+        let mut queue: BTreeSet<_> = (0..neighbours.len()).map(|index| key(index, 0)).collect();
+        let mut taken = Vec::new();
+        while let Some((_, _, Reverse(index))) = queue.pop_last() {
+            let colour = Colouring::lowest_free(&neighbours[index], &colours, &mut taken);
+            colours[index] = Some(colour);
+
+            for &neighbour in neighbours[index].iter() {
+                if colours[neighbour].is_none() && !neighbour_colours[neighbour].contains(&colour) {
+                    queue.remove(&key(neighbour, neighbour_colours[neighbour].len()));
+                    neighbour_colours[neighbour].insert(colour);
+                    queue.insert(key(neighbour, neighbour_colours[neighbour].len()));
+                }
+            }
+        }
+        Colouring::finish(colours)
+    }
+
+    // Lowest colour that no neighbour has, `taken` is reused between calls
+    fn lowest_free(neighbours: &[usize], colours: &[Option<usize>], taken: &mut Vec<bool>) -> usize {
+        taken.clear();
+        taken.resize(neighbours.len() + 1, false);
+        for colour in neighbours.iter().filter_map(|&neighbour| colours[neighbour]) {
+            if colour < taken.len() {
+                taken[colour] = true;
+            }
+        }
+        taken.iter().position(|taken| !taken).expect("One of degree + 1 colours is free")
+    }
+
+    fn finish(colours: Vec<Option<usize>>) -> VertexColouring {
+        let colours: Vec<usize> = colours.into_iter().map(|colour| colour.expect("Every node is coloured")).collect();
+        let count = colours.iter().max().map_or(0, |max| max + 1);
+        VertexColouring { colours: NodeMap::from_vec(colours), count }
+    }
+}
+
+impl ColouringSearch<'_> {
+    // Colours the remaining nodes, `coloured` of them are done and `used` colours appear so far
+    fn extend(&mut self, coloured: usize, used: usize) -> bool {
+        if coloured == self.colours.len() {
+            return true;
+        }
+
+        // Uncoloured node with the fewest colours left, ties go to the highest degree
+        let index = (0..self.colours.len())
+            .filter(|&index| self.colours[index].is_none())
+            .max_by_key(|&index| {
+                let saturation = self.conflicts[index].iter().filter(|&&count| count > 0).count();
+                (saturation, self.neighbours[index].len(), Reverse(index))
+            })
+            .expect("Some node is not coloured");
+
+        // Unused colours are interchangeable, so only the first of them is tried
+        for colour in 0..self.colour_limit.min(used + 1) {
+            if self.conflicts[index][colour] > 0 {
+                continue;
+            }
+
+            self.set_colour(index, colour);
+            if self.extend(coloured + 1, used.max(colour + 1)) {
+                return true;
+            }
+            self.clear_colour(index);
+        }
+        false
+    }
+
+    fn set_colour(&mut self, index: usize, colour: usize) {
+        for &neighbour in self.neighbours[index].iter() {
+            self.conflicts[neighbour][colour] += 1;
+        }
+        self.colours[index] = Some(colour);
+    }
+
+    fn clear_colour(&mut self, index: usize) {
+        let colour = self.colours[index].take().expect("Node is coloured");
+        for &neighbour in self.neighbours[index].iter() {
+            self.conflicts[neighbour][colour] -= 1;
+        }
+    }
+}
@@ -0,0 +1,316 @@
+use crate::node_map::NodeMap;
+use crate::path_finder::{NodeIndexable, EdgeIndexable};
+use crate::rng::Rng;
+use crate::weighted_graph::{Node, Edge};
+
+pub struct Community;
+
+// Community of every node, numbered from 0, and modularity of the partition
+#[derive(Debug, Clone, PartialEq)]
+pub struct Communities {
+    pub labels: NodeMap<usize>,
+    pub count: usize,
+    pub modularity: f64
+}
+
+// Undirected graph with weights as f64, every edge is listed from both of its ends.
+// Self-loops are kept apart and count twice into the strength of their node.
+struct WeightedAdjacency {
+    neighbours: Vec<Vec<(usize, f64)>>,
+    loops: Vec<f64>,
+    strengths: Vec<f64>,
+    total_weight: f64
+}
+
+// Total weight of edges from one node to every label of its neighbours
+struct LabelWeights {
+    weights: Vec<f64>,
+    seen: Vec<bool>,
+    labels: Vec<usize>
+}
+
+// Smallest modularity gain worth moving a node for, relative to the total weight
+const MIN_GAIN: f64 = 1e-12;
+
+impl Community {
+
+    ///
+    /// Finds communities of an undirected graph with the Louvain method: nodes move to the neighbouring
+    /// community that increases modularity the most, then communities are merged into single nodes
+    /// and the same is repeated until nothing moves. Higher resolution gives smaller communities.
+    /// Nodes are visited in random order, the same seed gives the same communities.
+    /// O(E log V) in practice
+    ///
+    /// # Panics
+    /// Panics if the graph is directed or if any weight is negative.
+    ///
+    pub fn louvain<'a, W, G>(graph: &'a G, resolution: f64, seed: u64) -> Communities
+        where G: EdgeIndexable<'a, Node, Edge, W> + NodeIndexable<Node>, W: Copy + num_traits::ToPrimitive {
+
+        let original = WeightedAdjacency::new(graph);
+        let mut rng = Rng::new(seed);
+
+        // Every node of the graph is a member of a node of the current level
+        let mut labels: Vec<usize> = (0..original.len()).collect();
+        let mut aggregated: Option<WeightedAdjacency> = None;
+        loop {
+            let level = aggregated.as_ref().unwrap_or(&original);
+            let mut level_labels: Vec<usize> = (0..level.len()).collect();
+            if !Community::move_nodes(level, &mut level_labels, resolution, &mut rng) {
+                break;
+            }
+
+            let count = Community::renumber(&mut level_labels);
+            for label in labels.iter_mut() {
+                *label = level_labels[*label];
+            }
+            aggregated = Some(level.aggregate(&level_labels, count));
+        }
+
+        let count = aggregated.as_ref().unwrap_or(&original).len();
+        let modularity = original.modularity(&labels, resolution);
+        Communities { labels: NodeMap::from_vec(labels), count, modularity }
+    }
+
+    ///
+    /// Finds communities of an undirected graph with asynchronous label propagation: every node, in random
+    /// order, takes the label with the highest total weight among its neighbours, until no label changes.
+    /// Ties are broken at random, the same seed gives the same communities.
+    /// O(E) per round
+    ///
+    /// # Panics
+    /// Panics if the graph is directed or if any weight is negative.
+    ///
+    pub fn label_propagation<'a, W, G>(graph: &'a G, seed: u64) -> Communities
+        where G: EdgeIndexable<'a, Node, Edge, W> + NodeIndexable<Node>, W: Copy + num_traits::ToPrimitive {
+
+        let adjacency = WeightedAdjacency::new(graph);
+        let mut rng = Rng::new(seed);
+        let mut labels: Vec<usize> = (0..adjacency.len()).collect();
+        let mut label_weights = LabelWeights::new(adjacency.len());
+        let mut order: Vec<usize> = (0..adjacency.len()).collect();
+
+        let mut changed = true;
+        while changed {
+            changed = false;
+            rng.shuffle(&mut order);
+            for &node in order.iter() {
+                label_weights.collect(&adjacency, node, &labels);
+                let heaviest = label_weights.labels.iter().map(|&label| label_weights.weights[label]).fold(f64::NEG_INFINITY, f64::max);
+                let best: Vec<usize> = label_weights.labels.iter().copied().filter(|&label| label_weights.weights[label] == heaviest).collect();
+                if !best.is_empty() && !best.contains(&labels[node]) {
+                    labels[node] = best[rng.below(best.len())];
+                    changed = true;
+                }
+                label_weights.clear();
+            }
+        }
+
+        let count = Community::renumber(&mut labels);
+        let modularity = adjacency.modularity(&labels, 1.0);
+        Communities { labels: NodeMap::from_vec(labels), count, modularity }
+    }
+
+    ///
+    /// Modularity of a partition of an undirected graph into communities given by labels:
+    /// the share of weight inside communities less the share expected at random,
+    /// scaled by resolution. 0 for a graph without edges.
+    /// O(V + E)
+    ///
+    /// # Panics
+    /// Panics if the graph is directed, if any weight is negative or if labels don't belong to the graph.
+    ///
+    pub fn modularity<'a, W, G>(graph: &'a G, labels: &NodeMap<usize>, resolution: f64) -> f64
+        where G: EdgeIndexable<'a, Node, Edge, W> + NodeIndexable<Node>, W: Copy + num_traits::ToPrimitive {
+
+        let adjacency = WeightedAdjacency::new(graph);
+        assert_eq!(adjacency.len(), labels.len(), "Labels don't belong to the graph");
+
+        let mut labels = labels.values().to_vec();
+        Community::renumber(&mut labels);
+        adjacency.modularity(&labels, resolution)
+    }
+
+    // Local moving phase of Louvain, starting from every node in its own community.
+    // Returns whether any node moved.
+    fn move_nodes(adjacency: &WeightedAdjacency, labels: &mut [usize], resolution: f64, rng: &mut Rng) -> bool {
+        if adjacency.total_weight == 0.0 {
+            return false;
+        }
+
+        // Total strength of every community
+        let mut totals = adjacency.strengths.clone();
+        let mut label_weights = LabelWeights::new(adjacency.len());
+        let mut order: Vec<usize> = (0..adjacency.len()).collect();
+        let min_gain = MIN_GAIN * adjacency.total_weight;
+
+        let mut moved_any = false;
+        loop {
+            let mut moved = false;
+            rng.shuffle(&mut order);
+            for &node in order.iter() {
+                let current = labels[node];
+                let strength = adjacency.strengths[node];
+                totals[current] -= strength;
+                label_weights.collect(adjacency, node, labels);
+
+                // Gain of joining a community, up to a constant factor
+                let gain = |label: usize| label_weights.weights[label] - resolution * totals[label] * strength / (2.0 * adjacency.total_weight);
+                let mut best = (current, gain(current));
+                for &label in label_weights.labels.iter() {
+                    let label_gain = gain(label);
+                    if label_gain > best.1 + min_gain {
+                        best = (label, label_gain);
+                    }
+                }
+
+                labels[node] = best.0;
+                totals[best.0] += strength;
+                moved |= best.0 != current;
+                label_weights.clear();
+            }
+
+            if !moved {
+                return moved_any;
+            }
+            moved_any = true;
+        }
+    }
+
+    // Numbers labels from 0 in order of appearance, returns how many there are
+    fn renumber(labels: &mut [usize]) -> usize {
+        let mut numbers = vec![None; labels.iter().max().map_or(0, |max| max + 1)];
+        let mut count = 0;
+        for label in labels.iter_mut() {
+            *label = *numbers[*label].get_or_insert_with(|| {
+                count += 1;
+                count - 1
+            });
+        }
+        count
+    }
+}
+
+impl WeightedAdjacency {
+    fn new<'a, W, G>(graph: &'a G) -> Self
+        where G: EdgeIndexable<'a, Node, Edge, W> + NodeIndexable<Node>, W: Copy + num_traits::ToPrimitive {
+
+        assert!(!graph.is_directed(), "Communities are only defined for undirected graphs");
+
+        let mut adjacency = WeightedAdjacency::with_nodes(graph.node_count());
+        let mut listed = vec![false; graph.edge_count()];
+        for index in 0..graph.node_count() {
+            for (edge, neighbour, weight) in graph.get_edges(graph.node_at(index)) {
+                let edge_index = graph.edge_index(edge);
+                if !listed[edge_index] {
+                    listed[edge_index] = true;
+                    let weight = weight.to_f64().expect("Weight can't be converted to f64");
+                    assert!(weight >= 0.0, "Weights can't be negative");
+                    adjacency.add_edge(index, graph.index_of(neighbour), weight);
+                }
+            }
+        }
+        adjacency
+    }
+
+    fn with_nodes(node_count: usize) -> Self {
+        WeightedAdjacency {
+            neighbours: vec![Vec::new(); node_count],
+            loops: vec![0.0; node_count],
+            strengths: vec![0.0; node_count],
+            total_weight: 0.0
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.neighbours.len()
+    }
+
+    fn add_edge(&mut self, source: usize, target: usize, weight: f64) {
+        if source == target {
+            self.loops[source] += weight;
+        } else {
+            self.neighbours[source].push((target, weight));
+            self.neighbours[target].push((source, weight));
+        }
+        self.strengths[source] += weight;
+        self.strengths[target] += weight;
+        self.total_weight += weight;
+    }
+
+    // Graph with a node for every community, edges inside communities become self-loops
+    fn aggregate(&self, labels: &[usize], count: usize) -> WeightedAdjacency {
+        let mut aggregated = WeightedAdjacency::with_nodes(count);
+        for (node, neighbours) in self.neighbours.iter().enumerate() {
+            aggregated.add_edge(labels[node], labels[node], self.loops[node]);
+            for &(neighbour, weight) in neighbours.iter().filter(|(neighbour, _)| node < *neighbour) {
+                aggregated.add_edge(labels[node], labels[neighbour], weight);
+            }
+        }
+
+        // Join parallel edges between the same communities
+        for neighbours in aggregated.neighbours.iter_mut() {
+            neighbours.sort_unstable_by_key(|(neighbour, _)| *neighbour);
+            neighbours.dedup_by(|(neighbour, weight), (kept, kept_weight)| {
+                if neighbour == kept {
+                    *kept_weight += *weight;
+                }
+                neighbour == kept
+            });
+        }
+        aggregated
+    }
+
+    // Labels have to be numbered from 0
+    fn modularity(&self, labels: &[usize], resolution: f64) -> f64 {
+        if self.total_weight == 0.0 {
+            return 0.0;
+        }
+
+        let count = labels.iter().max().map_or(0, |max| max + 1);
+        let mut inside = vec![0.0; count];
+        let mut strengths = vec![0.0; count];
+        for (node, neighbours) in self.neighbours.iter().enumerate() {
+            let label = labels[node];
+            inside[label] += self.loops[node];
+            strengths[label] += self.strengths[node];
+            for &(neighbour, weight) in neighbours.iter() {
+                if labels[neighbour] == label {
+                    // Counted from both ends
+                    inside[label] += weight / 2.0;
+                }
+            }
+        }
+
+        let total = self.total_weight;
+        inside.iter().zip(strengths.iter())
+            .map(|(inside, strength)| inside / total - resolution * (strength / (2.0 * total)).powi(2))
+            .sum()
+    }
+}
+
+impl LabelWeights {
+    fn new(label_count: usize) -> Self {
+        LabelWeights { weights: vec![0.0; label_count], seen: vec![false; label_count], labels: Vec::new() }
+    }
+
+    // Adds up weights of edges to neighbours by their labels, self-loops don't count
+    fn collect(&mut self, adjacency: &WeightedAdjacency, node: usize, labels: &[usize]) {
+        for &(neighbour, weight) in adjacency.neighbours[node].iter() {
+            let label = labels[neighbour];
+            if !self.seen[label] {
+                self.seen[label] = true;
+                self.labels.push(label);
+            }
+            self.weights[label] += weight;
+        }
+    }
+
+    fn clear(&mut self) {
+        for &label in self.labels.iter() {
+            self.weights[label] = 0.0;
+            self.seen[label] = false;
+        }
+        self.labels.clear();
+    }
+}
@@ -0,0 +1,412 @@
+use std::collections::{HashSet, VecDeque};
+use std::ops::Index;
+
+use crate::graph::Graph;
+use crate::node_map::NodeMap;
+use crate::path_finder::{PathFindable, NodeIndexable, EdgeIndexable};
+use crate::union_find::UnionFind;
+use crate::weighted_graph::{Node, Edge};
+
+pub struct Connectivity;
+
+// Component of every node. Components are numbered 0..count() in order of their lowest node index.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Components {
+    component: NodeMap<usize>,
+    count: usize
+}
+
+impl Components {
+    // Numbers components in order of their lowest node index, whatever the ids were
+    fn from_ids(ids: Vec<usize>) -> Self {
+        let mut renumbered: Vec<Option<usize>> = vec![None; ids.len()];
+        let mut count = 0;
+        let component = ids.iter().map(|id| {
+            *renumbered[*id].get_or_insert_with(|| {
+                count += 1;
+                count - 1
+            })
+        }).collect();
+
+        Components { component: NodeMap::from_vec(component), count }
+    }
+
+    // Number of components
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    pub fn component_of(&self, node: Node) -> usize {
+        self.component[node]
+    }
+
+    // Nodes of every component, ordered by node index
+    pub fn members(&self) -> Vec<Vec<Node>> {
+        let mut members = vec![Vec::new(); self.count];
+        for (node, component) in self.component.iter() {
+            members[*component].push(node);
+        }
+        members
+    }
+
+    pub fn as_node_map(&self) -> &NodeMap<usize> {
+        &self.component
+    }
+}
+
+impl Index<Node> for Components {
+    type Output = usize;
+
+    fn index(&self, index: Node) -> &Self::Output {
+        &self.component[index]
+    }
+}
+
+impl Connectivity {
+
+    ///
+    /// Finds connected components of an undirected graph using BFS.
+    /// For directed graphs use `weakly_connected_components`.
+    /// O(V + E)
+    ///
+    /// # Panics
+    /// Panics if the graph is directed.
+    ///
+    pub fn connected_components<'a, Dist, G>(graph: &'a G) -> Components
+        where G: EdgeIndexable<'a, Node, Edge, Dist> + NodeIndexable<Node> {
+
+        assert!(!graph.is_directed(), "Connected components are only defined for undirected graphs, use weakly_connected_components");
+
+        let mut ids: Vec<Option<usize>> = vec![None; graph.node_count()];
+        let mut queue = VecDeque::new();
+
+        for start in 0..graph.node_count() {
+            if ids[start].is_some() {
+                continue;
+            }
+
+            ids[start] = Some(start);
+            queue.push_back(graph.node_at(start));
+            while let Some(node) = queue.pop_front() {
+                for (_, neighbour, _) in graph.get_edges(node) {
+                    let index = graph.index_of(neighbour);
+                    if ids[index].is_none() {
+                        ids[index] = Some(start);
+                        queue.push_back(neighbour);
+                    }
+                }
+            }
+        }
+
+        Components::from_ids(ids.into_iter().map(|id| id.expect("Node was not labelled")).collect())
+    }
+
+    ///
+    /// Finds components of a directed graph when edge directions are ignored, using union-find.
+    /// O(V + E α(V))
+    ///
+    pub fn weakly_connected_components<'a, Dist, G>(graph: &'a G) -> Components
+        where G: PathFindable<'a, Node, Dist> + NodeIndexable<Node> {
+
+        let mut sets = UnionFind::new(graph.node_count());
+        for node in graph.nodes() {
+            for (neighbour, _) in graph.get_neighbours(node) {
+                sets.union(graph.index_of(node), graph.index_of(neighbour));
+            }
+        }
+
+        Components::from_ids((0..graph.node_count()).map(|index| sets.find(index)).collect())
+    }
+
+    ///
+    /// Finds strongly connected components of a directed graph: maximal sets of nodes
+    /// that can all reach each other. Uses Tarjan's algorithm without recursion.
+    /// O(V + E)
+    ///
+    pub fn strongly_connected_components<'a, Dist, G>(graph: &'a G) -> Components
+        where G: PathFindable<'a, Node, Dist> + NodeIndexable<Node> {
+
+        let ids = Connectivity::tarjan(graph.node_count(), |index| {
+            graph.get_neighbours(graph.node_at(index)).map(|(neighbour, _)| graph.index_of(neighbour))
+        });
+        Components::from_ids(ids)
+    }
+
+    ///
+    /// Builds the condensation of a directed graph: a directed acyclic graph with a node for every
+    /// strongly connected component, holding its members, and an edge between two components
+    /// if any edge leads between their members. Node n of the result holds component n
+    /// of `strongly_connected_components`.
+    /// O(V + E)
+    ///
+    pub fn condensation<'a, Dist, G>(graph: &'a G) -> Graph<Vec<Node>>
+        where G: PathFindable<'a, Node, Dist> + NodeIndexable<Node> {
+
+        let components = Connectivity::strongly_connected_components(graph);
+
+        let mut dag = Graph::new_directed();
+        let dag_nodes: Vec<Node> = components.members()
+            .into_iter()
+            .map(|members| dag.add_node(members))
+            .collect();
+
+        let mut added = HashSet::new();
+        for node in graph.nodes() {
+            for (neighbour, _) in graph.get_neighbours(node) {
+                let (source, target) = (components[node], components[neighbour]);
+                if source != target && added.insert((source, target)) {
+                    dag.add_edge(dag_nodes[source], dag_nodes[target]);
+                }
+            }
+        }
+
+        dag
+    }
+
+    // Iterative Tarjan's algorithm over node indices, returns an id for every node shared by
+    // the nodes of one strongly connected component. Components get ids in reverse topological order.
+    pub(crate) fn tarjan<I: Iterator<Item = usize>>(node_count: usize, neighbours: impl Fn(usize) -> I) -> Vec<usize> {
+        const UNVISITED: usize = usize::MAX;
+
+        let mut order = vec![UNVISITED; node_count];
+        let mut low_link = vec![0; node_count];
+        let mut on_stack = vec![false; node_count];
+        let mut component = vec![UNVISITED; node_count];
+        let mut stack = Vec::new();
+        let mut call_stack: Vec<(usize, I)> = Vec::new();
+        let mut visited_count = 0;
+        let mut component_count = 0;
+
+        for start in 0..node_count {
+            if order[start] != UNVISITED {
+                continue;
+            }
+
+            order[start] = visited_count;
+            low_link[start] = visited_count;
+            visited_count += 1;
+            stack.push(start);
+            on_stack[start] = true;
+            call_stack.push((start, neighbours(start)));
+
+            while let Some((node, node_neighbours)) = call_stack.last_mut() {
+                let node = *node;
+
+                if let Some(neighbour) = node_neighbours.next() {
+                    if order[neighbour] == UNVISITED {
+                        // Go deeper, as the recursive version would
+                        order[neighbour] = visited_count;
+                        low_link[neighbour] = visited_count;
+                        visited_count += 1;
+                        stack.push(neighbour);
+                        on_stack[neighbour] = true;
+                        call_stack.push((neighbour, neighbours(neighbour)));
+                    } else if on_stack[neighbour] {
+                        low_link[node] = low_link[node].min(order[neighbour]);
+                    }
+                    continue;
+                }
+
+                // All neighbours done, return to the caller
+                call_stack.pop();
+                if let Some((parent, _)) = call_stack.last() {
+                    low_link[*parent] = low_link[*parent].min(low_link[node]);
+                }
+
+                // The node is the root of its component, which sits on top of the stack
+                if low_link[node] == order[node] {
+                    loop {
+                        let member = stack.pop().expect("Component root is not on the stack");
+                        on_stack[member] = false;
+                        component[member] = component_count;
+                        if member == node {
+                            break;
+                        }
+                    }
+                    component_count += 1;
+                }
+            }
+        }
+
+        component
+    }
+
+    ///
+    /// Finds bridges of an undirected graph: edges whose removal disconnects their nodes.
+    /// An edge with a parallel copy is never a bridge. Each bridge is returned as one of
+    /// the two copies listed by its nodes.
+    /// O(V + E)
+    ///
+    /// # Panics
+    /// Panics if the graph is directed.
+    ///
+    pub fn bridges<'a, Dist, G>(graph: &'a G) -> Vec<Edge>
+        where G: EdgeIndexable<'a, Node, Edge, Dist> + NodeIndexable<Node> {
+        Connectivity::biconnectivity(graph).bridges
+    }
+
+    ///
+    /// Finds articulation points of an undirected graph: nodes whose removal disconnects
+    /// the rest of their component. Nodes are returned in order of their index.
+    /// O(V + E)
+    ///
+    /// # Panics
+    /// Panics if the graph is directed.
+    ///
+    pub fn articulation_points<'a, Dist, G>(graph: &'a G) -> Vec<Node>
+        where G: EdgeIndexable<'a, Node, Edge, Dist> + NodeIndexable<Node> {
+
+        let is_articulation_point = Connectivity::biconnectivity(graph).is_articulation_point;
+        (0..graph.node_count())
+            .filter(|index| is_articulation_point[*index])
+            .map(|index| graph.node_at(index))
+            .collect()
+    }
+
+    ///
+    /// Splits edges of an undirected graph into biconnected components: maximal sets of edges
+    /// where any two edges lie on a common simple cycle. A bridge forms a component on its own,
+    /// self-loops belong to no component. Each edge is returned as one of the two copies listed by its nodes.
+    /// O(V + E)
+    ///
+    /// # Panics
+    /// Panics if the graph is directed.
+    ///
+    pub fn biconnected_components<'a, Dist, G>(graph: &'a G) -> Vec<Vec<Edge>>
+        where G: EdgeIndexable<'a, Node, Edge, Dist> + NodeIndexable<Node> {
+        Connectivity::biconnectivity(graph).components
+    }
+
+    // Iterative Hopcroft-Tarjan DFS computing low links. The edge a node was discovered through is
+    // recognised by its index, so its reverse copy is skipped while parallel edges still count as back edges.
+    fn biconnectivity<'a, Dist, G>(graph: &'a G) -> Biconnectivity
+        where G: EdgeIndexable<'a, Node, Edge, Dist> + NodeIndexable<Node> {
+
+        assert!(!graph.is_directed(), "Biconnectivity is only defined for undirected graphs");
+
+        const UNVISITED: usize = usize::MAX;
+
+        let mut order = vec![UNVISITED; graph.node_count()];
+        let mut low_link = vec![0; graph.node_count()];
+        let mut result = Biconnectivity { bridges: Vec::new(), is_articulation_point: vec![false; graph.node_count()], components: Vec::new() };
+        let mut edge_stack: Vec<Edge> = Vec::new();
+        let mut visited_count = 0;
+
+        for root in 0..graph.node_count() {
+            if order[root] != UNVISITED {
+                continue;
+            }
+
+            order[root] = visited_count;
+            low_link[root] = visited_count;
+            visited_count += 1;
+            let mut root_children = 0;
+
+            // Node with the edge it was discovered through and its remaining edges
+            let mut call_stack: Vec<(usize, Option<Edge>, _)> = vec![(root, None, graph.get_edges(graph.node_at(root)))];
+
+            while let Some((node, parent_edge, edges)) = call_stack.last_mut() {
+                let (node, parent_edge) = (*node, *parent_edge);
+
+                if let Some((edge, neighbour, _)) = edges.next() {
+                    let neighbour = graph.index_of(neighbour);
+                    let is_parent_edge = parent_edge.is_some_and(|parent_edge| graph.edge_index(parent_edge) == graph.edge_index(edge));
+                    if neighbour == node || is_parent_edge {
+                        continue;
+                    }
+
+                    if order[neighbour] == UNVISITED {
+                        order[neighbour] = visited_count;
+                        low_link[neighbour] = visited_count;
+                        visited_count += 1;
+                        if node == root {
+                            root_children += 1;
+                        }
+                        edge_stack.push(edge);
+                        call_stack.push((neighbour, Some(edge), graph.get_edges(graph.node_at(neighbour))));
+                    } else if order[neighbour] < order[node] {
+                        // Back edge to an ancestor, seen from the descendant's side first
+                        low_link[node] = low_link[node].min(order[neighbour]);
+                        edge_stack.push(edge);
+                    }
+                    continue;
+                }
+
+                call_stack.pop();
+                let (Some(parent_edge), Some((parent, _, _))) = (parent_edge, call_stack.last()) else {
+                    continue;
+                };
+                let parent = *parent;
+                low_link[parent] = low_link[parent].min(low_link[node]);
+
+                // Nothing below the node reaches above its parent
+                if low_link[node] >= order[parent] {
+                    if parent != root {
+                        result.is_articulation_point[parent] = true;
+                    }
+                    if low_link[node] > order[parent] {
+                        result.bridges.push(parent_edge);
+                    }
+
+                    let mut component = Vec::new();
+                    while let Some(edge) = edge_stack.pop() {
+                        component.push(edge);
+                        if graph.edge_index(edge) == graph.edge_index(parent_edge) {
+                            break;
+                        }
+                    }
+                    result.components.push(component);
+                }
+            }
+
+            if root_children > 1 {
+                result.is_articulation_point[root] = true;
+            }
+        }
+
+        result
+    }
+
+    ///
+    /// Copies every component into its own graph, in order of component ids.
+    /// Nodes keep their order within a component, edges between components are left out.
+    /// O(V + E)
+    ///
+    pub fn extract_components<T: Clone>(graph: &Graph<T>, components: &Components) -> Vec<Graph<T>> {
+        let mut extracted: Vec<Graph<T>> = (0..components.count())
+            .map(|_| if graph.is_directed() { Graph::new_directed() } else { Graph::new_undirected() })
+            .collect();
+
+        // Component and copy of every node, by node index
+        let copies: Vec<(usize, Node)> = graph.nodes()
+            .map(|(node, value)| {
+                let component = components[node];
+                (component, extracted[component].add_node(value.clone()))
+            })
+            .collect();
+
+        // Undirected edges are listed by both of their nodes
+        let mut copied = vec![false; graph.edge_count()];
+        for edge in graph.edges() {
+            if copied[edge.uid] {
+                continue;
+            }
+            copied[edge.uid] = true;
+
+            // Edges between strongly connected components belong to none of them
+            let (component, source) = copies[graph.index_of(edge.source)];
+            let (target_component, target) = copies[graph.index_of(edge.target)];
+            if component == target_component {
+                extracted[component].add_edge(source, target);
+            }
+        }
+
+        extracted
+    }
+}
+
+struct Biconnectivity {
+    bridges: Vec<Edge>,
+    is_articulation_point: Vec<bool>,
+    components: Vec<Vec<Edge>>
+}
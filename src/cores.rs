@@ -0,0 +1,113 @@
+use crate::graph::Graph;
+use crate::node_map::NodeMap;
+use crate::path_finder::{NodeIndexable, EdgeIndexable};
+use crate::weighted_graph::{Node, Edge};
+
+pub struct Cores;
+
+// Core number of every node: the largest k such that the node belongs to the k-core,
+// the maximal subgraph where every node has at least k neighbours.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CoreNumbers {
+    pub core: NodeMap<usize>,
+
+    // Largest core number
+    pub degeneracy: usize,
+
+    // Nodes in order of removal, each of them has at most `degeneracy` neighbours later in the order
+    pub ordering: Vec<Node>
+}
+
+impl CoreNumbers {
+    // Nodes of the k-core, ordered by node index
+    pub fn k_core(&self, k: usize) -> Vec<Node> {
+        self.core.iter()
+            .filter(|(_, core)| **core >= k)
+            .map(|(node, _)| node)
+            .collect()
+    }
+}
+
+impl Cores {
+
+    ///
+    /// Finds core numbers of an undirected graph by repeatedly removing a node of the lowest
+    /// remaining degree, kept in a bucket queue (Batagelj and Zaversnik).
+    /// Self-loops and parallel edges are ignored.
+    /// O(V + E)
+    ///
+    /// # Panics
+    /// Panics if the graph is directed.
+    ///
+    pub fn core_numbers<'a, W, G>(graph: &'a G) -> CoreNumbers
+        where G: EdgeIndexable<'a, Node, Edge, W> + NodeIndexable<Node> {
+
+        assert!(!graph.is_directed(), "Cores are only defined for undirected graphs");
+
+        let neighbours = Cores::neighbours(graph);
+        let mut degrees: Vec<usize> = neighbours.iter().map(Vec::len).collect();
+        let max_degree = degrees.iter().copied().max().unwrap_or(0);
+
+        // Nodes sorted by degree, with the position where every degree starts and the position of every node
+        let mut starts = vec![0; max_degree + 2];
+        for &degree in degrees.iter() {
+            starts[degree + 1] += 1;
+        }
+        for degree in 1..starts.len() {
+            starts[degree] += starts[degree - 1];
+        }
+        let mut sorted = vec![0; degrees.len()];
+        let mut positions = vec![0; degrees.len()];
+        let mut next = starts.clone();
+        for (node, &degree) in degrees.iter().enumerate() {
+            positions[node] = next[degree];
+            sorted[next[degree]] = node;
+            next[degree] += 1;
+        }
+
+        // Removing a node moves every neighbour with a higher degree to the front of its bucket,
+        // then shrinks the bucket by one
+        for position in 0..sorted.len() {
+            let node = sorted[position];
+            for &neighbour in neighbours[node].iter() {
+                let degree = degrees[neighbour];
+                if degree > degrees[node] {
+                    let first = sorted[starts[degree]];
+                    sorted.swap(positions[neighbour], starts[degree]);
+                    positions.swap(neighbour, first);
+                    starts[degree] += 1;
+                    degrees[neighbour] -= 1;
+                }
+            }
+        }
+
+        CoreNumbers {
+            degeneracy: degrees.iter().copied().max().unwrap_or(0),
+            ordering: sorted.into_iter().map(|index| graph.node_at(index)).collect(),
+            core: NodeMap::from_vec(degrees)
+        }
+    }
+
+    ///
+    /// Copies the k-core of the graph, nodes keep their order.
+    /// O(V + E)
+    ///
+    pub fn k_core<T: Clone>(graph: &Graph<T>, cores: &CoreNumbers, k: usize) -> Graph<T> {
+        graph.induced_subgraph(&cores.k_core(k))
+    }
+
+    // Distinct neighbours of every node of an undirected graph, without the node itself.
+    // `last` marks the node a neighbour was last listed for, so no sorting is needed.
+    fn neighbours<'a, W, G>(graph: &'a G) -> Vec<Vec<usize>>
+        where G: EdgeIndexable<'a, Node, Edge, W> + NodeIndexable<Node> {
+
+        let mut last = vec![None; graph.node_count()];
+        (0..graph.node_count()).map(|index| {
+            last[index] = Some(index);
+            graph.get_edges(graph.node_at(index))
+                .map(|(_, neighbour, _)| graph.index_of(neighbour))
+                .filter(|&neighbour| last[neighbour].replace(index) != Some(index))
+                .collect()
+        }).collect()
+    }
+}
@@ -0,0 +1,370 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::connectivity::Connectivity;
+use crate::path_finder::{NodeIndexable, EdgeIndexable};
+use crate::weighted_graph::{Node, Edge};
+
+pub struct Cycles;
+
+type Edges<'a, W> = Box<dyn Iterator<Item=(Edge, Node, W)> + 'a>;
+
+// Lazy iterator over elementary cycles of a directed graph, created by `Cycles::simple_cycles`.
+// Every node of a cycle has an edge to the next one, and the last node has an edge to the first.
+pub struct SimpleCycles {
+    nodes: Vec<Node>,
+
+    // Distinct neighbours of every node without the node itself, self-loops are listed apart
+    neighbours: Vec<Vec<usize>>,
+    self_loops: Vec<usize>,
+    length_limit: Option<usize>,
+
+    // Strongly connected parts of the graph still to search, each of them has at least two nodes
+    components: Vec<Vec<usize>>,
+    search: Option<CircuitSearch>,
+
+    // Nodes of the component being searched
+    allowed: Vec<bool>,
+    on_path: Vec<bool>
+}
+
+// Search for cycles through the first node of a component, bounded by a cycle length
+// (Johnson, with locks instead of blocked nodes as in Gupta and Suzumura).
+// A node is locked for paths at least as long as its lock, paths shorter than that may still
+// lead to new cycles through it.
+struct CircuitSearch {
+    component: Vec<usize>,
+    length_bound: usize,
+    path: Vec<usize>,
+
+    // Position of the next neighbour to try for every node of the path
+    positions: Vec<usize>,
+
+    // Length of the shortest way back to the start found from every node of the path
+    closing_lengths: Vec<usize>,
+    locks: HashMap<usize, usize>,
+
+    // Nodes waiting for the key node to be unlocked
+    waiting: HashMap<usize, HashSet<usize>>
+}
+
+impl Cycles {
+
+    ///
+    /// Checks whether the graph has a cycle. In undirected graphs an edge can't be used twice,
+    /// so only self-loops, parallel edges and cycles of three or more nodes count.
+    /// O(V + E)
+    ///
+    pub fn has_cycle<'a, W, G>(graph: &'a G) -> bool
+        where G: EdgeIndexable<'a, Node, Edge, W> + NodeIndexable<Node> {
+        Cycles::find_cycle(graph).is_some()
+    }
+
+    ///
+    /// Finds a cycle of a directed or undirected graph using DFS. Returns its edges in order,
+    /// so every edge ends where the next one starts and the last one ends where the first one starts.
+    /// In undirected graphs an edge can't be used twice.
+    /// O(V + E)
+    ///
+    pub fn find_cycle<'a, W, G>(graph: &'a G) -> Option<Vec<Edge>>
+        where G: EdgeIndexable<'a, Node, Edge, W> + NodeIndexable<Node> {
+
+        const UNVISITED: usize = usize::MAX;
+        const FINISHED: usize = usize::MAX - 1;
+
+        // Position on the DFS stack of every node that is on it
+        let mut positions = vec![UNVISITED; graph.node_count()];
+
+        // Nodes on the stack with the edge they were reached through and their edges left to check
+        let mut stack: Vec<(usize, Option<Edge>, Edges<'a, W>)> = Vec::new();
+
+        for start in 0..graph.node_count() {
+            if positions[start] != UNVISITED {
+                continue;
+            }
+
+            positions[start] = 0;
+            stack.push((start, None, graph.get_edges(graph.node_at(start))));
+            while let Some((node, incoming, edges)) = stack.last_mut() {
+                let (node, incoming) = (*node, *incoming);
+                let Some((edge, neighbour, _)) = edges.next() else {
+                    positions[node] = FINISHED;
+                    stack.pop();
+                    continue;
+                };
+
+                // Going back along an undirected edge doesn't close a cycle
+                if !graph.is_directed() && incoming.is_some_and(|incoming| graph.edge_index(incoming) == graph.edge_index(edge)) {
+                    continue;
+                }
+
+                let neighbour = graph.index_of(neighbour);
+                match positions[neighbour] {
+                    UNVISITED => {
+                        positions[neighbour] = stack.len();
+                        stack.push((neighbour, Some(edge), graph.get_edges(graph.node_at(neighbour))));
+                    },
+                    FINISHED => (),
+                    position => {
+                        let mut cycle: Vec<Edge> = stack[position + 1..].iter()
+                            .map(|(_, incoming, _)| incoming.expect("Only the root has no incoming edge"))
+                            .collect();
+                        cycle.push(edge);
+                        return Some(cycle);
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    ///
+    /// Finds a cycle basis of an undirected graph: every cycle of the graph is a combination of
+    /// cycles of the basis. Every edge outside of a BFS spanning forest closes one of them.
+    /// Every node of a cycle has an edge to the next one, and the last node has an edge to the first,
+    /// a self-loop gives a cycle of one node and parallel edges a cycle of two.
+    /// O(V * E)
+    ///
+    /// # Panics
+    /// Panics if the graph is directed.
+    ///
+    pub fn cycle_basis<'a, W, G>(graph: &'a G) -> Vec<Vec<Node>>
+        where G: EdgeIndexable<'a, Node, Edge, W> + NodeIndexable<Node> {
+
+        assert!(!graph.is_directed(), "Cycle basis is only defined for undirected graphs");
+
+        // Parent and depth of every node in the forest, with the edges that are part of it
+        let mut parents: Vec<Option<usize>> = vec![None; graph.node_count()];
+        let mut depths: Vec<Option<usize>> = vec![None; graph.node_count()];
+        let mut in_forest = vec![false; graph.edge_count()];
+        let mut queue = VecDeque::new();
+        for root in 0..graph.node_count() {
+            if depths[root].is_some() {
+                continue;
+            }
+
+            depths[root] = Some(0);
+            queue.push_back(root);
+            while let Some(index) = queue.pop_front() {
+                for (edge, neighbour, _) in graph.get_edges(graph.node_at(index)) {
+                    let neighbour = graph.index_of(neighbour);
+                    if depths[neighbour].is_none() {
+                        depths[neighbour] = depths[index].map(|depth| depth + 1);
+                        parents[neighbour] = Some(index);
+                        in_forest[graph.edge_index(edge)] = true;
+                        queue.push_back(neighbour);
+                    }
+                }
+            }
+        }
+
+        let depth = |index: usize| depths[index].expect("Every node is in the forest");
+        let parent = |index: usize| parents[index].expect("Only roots have no parent");
+
+        let mut listed = in_forest;
+        let mut basis = Vec::new();
+        for index in 0..graph.node_count() {
+            for (edge, neighbour, _) in graph.get_edges(graph.node_at(index)) {
+                let edge_index = graph.edge_index(edge);
+                if listed[edge_index] {
+                    continue;
+                }
+                listed[edge_index] = true;
+
+                // Climb from both ends of the edge to their lowest common ancestor
+                let (mut source, mut target) = (index, graph.index_of(neighbour));
+                let mut source_side = vec![source];
+                let mut target_side = Vec::new();
+                while source != target {
+                    if depth(source) >= depth(target) {
+                        source = parent(source);
+                        source_side.push(source);
+                    } else {
+                        target_side.push(target);
+                        target = parent(target);
+                    }
+                }
+                source_side.extend(target_side.into_iter().rev());
+                basis.push(source_side.into_iter().map(|index| graph.node_at(index)).collect());
+            }
+        }
+        basis
+    }
+
+    ///
+    /// Lazily lists every elementary cycle of a directed graph with Johnson's algorithm, searching
+    /// strongly connected components one start node at a time. Cycles longer than the length limit,
+    /// in nodes, are skipped without being searched. A self-loop is a cycle of one node,
+    /// parallel edges give the same cycle only once.
+    /// O((V + E) * (C + 1)) for C cycles
+    ///
+    /// # Panics
+    /// Panics if the graph is undirected.
+    ///
+    pub fn simple_cycles<'a, W, G>(graph: &'a G, length_limit: Option<usize>) -> SimpleCycles
+        where G: EdgeIndexable<'a, Node, Edge, W> + NodeIndexable<Node> {
+
+        assert!(graph.is_directed(), "Simple cycles are only listed for directed graphs");
+
+        let mut self_loops = Vec::new();
+        let neighbours = (0..graph.node_count()).map(|index| {
+            let mut list: Vec<usize> = graph.get_edges(graph.node_at(index))
+                .map(|(_, neighbour, _)| graph.index_of(neighbour))
+                .collect();
+            list.sort_unstable();
+            list.dedup();
+            if let Ok(position) = list.binary_search(&index) {
+                list.remove(position);
+                self_loops.push(index);
+            }
+            list
+        }).collect();
+        self_loops.reverse();
+
+        let mut cycles = SimpleCycles {
+            nodes: (0..graph.node_count()).map(|index| graph.node_at(index)).collect(),
+            neighbours,
+            self_loops,
+            length_limit,
+            components: Vec::new(),
+            search: None,
+            allowed: vec![false; graph.node_count()],
+            on_path: vec![false; graph.node_count()]
+        };
+        cycles.add_components((0..graph.node_count()).collect());
+        cycles
+    }
+}
+
+impl SimpleCycles {
+    // Queues strongly connected components of the subgraph of the members that can have cycles
+    fn add_components(&mut self, members: Vec<usize>) {
+        let local: HashMap<usize, usize> = members.iter().enumerate().map(|(position, &member)| (member, position)).collect();
+        let ids = Connectivity::tarjan(members.len(), |position| {
+            self.neighbours[members[position]].iter().filter_map(|neighbour| local.get(neighbour).copied())
+        });
+
+        let mut components = vec![Vec::new(); members.len()];
+        for (position, id) in ids.into_iter().enumerate() {
+            components[id].push(members[position]);
+        }
+        // Tarjan's ids are in reverse topological order, keep the first one on top
+        self.components.extend(components.into_iter().rev().filter(|component| component.len() > 1));
+    }
+}
+
+impl Iterator for SimpleCycles {
+    type Item = Vec<Node>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.length_limit == Some(0) {
+            return None;
+        }
+        if let Some(node) = self.self_loops.pop() {
+            return Some(vec![self.nodes[node]]);
+        }
+
+        loop {
+            if let Some(search) = self.search.as_mut() {
+                if let Some(cycle) = search.next_cycle(&self.neighbours, &self.allowed, &mut self.on_path) {
+                    return Some(cycle.into_iter().map(|index| self.nodes[index]).collect());
+                }
+
+                // Every cycle through the start node is listed, search what is left without it
+                let mut component = self.search.take().expect("Search is running").component;
+                for &member in component.iter() {
+                    self.allowed[member] = false;
+                }
+                component.remove(0);
+                self.add_components(component);
+                continue;
+            }
+
+            let component = self.components.pop()?;
+            for &member in component.iter() {
+                self.allowed[member] = true;
+            }
+            self.on_path[component[0]] = true;
+            self.search = Some(CircuitSearch::new(component, self.length_limit));
+        }
+    }
+}
+
+impl CircuitSearch {
+    fn new(component: Vec<usize>, length_limit: Option<usize>) -> Self {
+        // No cycle has more nodes than the component
+        let length_bound = length_limit.map_or(component.len(), |limit| limit.min(component.len()));
+        let start = component[0];
+        CircuitSearch {
+            component,
+            length_bound,
+            path: vec![start],
+            positions: vec![0],
+            closing_lengths: vec![length_bound],
+            locks: HashMap::from([(start, 0)]),
+            waiting: HashMap::new()
+        }
+    }
+
+    fn lock(&self, node: usize) -> usize {
+        self.locks.get(&node).copied().unwrap_or(self.length_bound)
+    }
+
+    // Continues the search until it finds the next cycle, the path never leaves allowed nodes
+    fn next_cycle(&mut self, neighbours: &[Vec<usize>], allowed: &[bool], on_path: &mut [bool]) -> Option<Vec<usize>> {
+        while let Some(&node) = self.path.last() {
+            let position = self.positions.last_mut().expect("Every node of the path has a position");
+            if let Some(&neighbour) = neighbours[node].get(*position) {
+                *position += 1;
+                if !allowed[neighbour] {
+                    continue;
+                }
+
+                if neighbour == self.path[0] {
+                    *self.closing_lengths.last_mut().expect("Path is not empty") = 1;
+                    return Some(self.path.clone());
+                }
+                if self.path.len() < self.lock(neighbour) {
+                    self.locks.insert(neighbour, self.path.len());
+                    on_path[neighbour] = true;
+                    self.path.push(neighbour);
+                    self.positions.push(0);
+                    self.closing_lengths.push(self.length_bound);
+                }
+                continue;
+            }
+
+            // All neighbours done, step back
+            self.path.pop();
+            self.positions.pop();
+            on_path[node] = false;
+            let closing_length = self.closing_lengths.pop().expect("Path is not empty");
+            if let Some(previous) = self.closing_lengths.last_mut() {
+                *previous = (*previous).min(closing_length);
+            }
+
+            if closing_length < self.length_bound {
+                self.relax_locks(node, closing_length, on_path);
+            } else {
+                for &neighbour in neighbours[node].iter().filter(|&&neighbour| allowed[neighbour]) {
+                    self.waiting.entry(neighbour).or_default().insert(node);
+                }
+            }
+        }
+        None
+    }
+
+    // The node can reach the start in `closing_length` edges, so paths that reach it or nodes
+    // waiting for it early enough may close cycles within the bound
+    fn relax_locks(&mut self, node: usize, closing_length: usize, on_path: &[bool]) {
+        let mut relaxed = vec![(closing_length, node)];
+        while let Some((closing_length, node)) = relaxed.pop() {
+            let lock = (self.length_bound + 1).saturating_sub(closing_length);
+            if self.lock(node) < lock {
+                self.locks.insert(node, lock);
+                if let Some(waiting) = self.waiting.get(&node) {
+                    relaxed.extend(waiting.iter().filter(|&&waiting| !on_path[waiting]).map(|&waiting| (closing_length + 1, waiting)));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,131 @@
+use crate::path_finder::{NodeIndexable, EdgeIndexable};
+use crate::weighted_graph::{Node, Edge};
+
+pub struct Euler;
+
+impl Euler {
+
+    ///
+    /// Checks whether the graph has an Eulerian circuit: a closed walk using every edge exactly once.
+    /// O(V + E)
+    ///
+    pub fn has_circuit<'a, W, G>(graph: &'a G) -> bool
+        where G: EdgeIndexable<'a, Node, Edge, W> + NodeIndexable<Node> {
+        Euler::circuit(graph).is_some()
+    }
+
+    ///
+    /// Checks whether the graph has an Eulerian path: a walk using every edge exactly once.
+    /// Every Eulerian circuit is also a path.
+    /// O(V + E)
+    ///
+    pub fn has_path<'a, W, G>(graph: &'a G) -> bool
+        where G: EdgeIndexable<'a, Node, Edge, W> + NodeIndexable<Node> {
+        Euler::path(graph).is_some()
+    }
+
+    ///
+    /// Finds an Eulerian circuit of a directed or undirected graph using Hierholzer's algorithm.
+    /// Every edge, parallel ones included, is used once, and each of them ends where the next one
+    /// starts; the last one ends where the first one starts. Empty for a graph without edges.
+    /// O(V + E)
+    ///
+    pub fn circuit<'a, W, G>(graph: &'a G) -> Option<Vec<Edge>>
+        where G: EdgeIndexable<'a, Node, Edge, W> + NodeIndexable<Node> {
+        Euler::trail(graph, true)
+    }
+
+    ///
+    /// Finds an Eulerian path of a directed or undirected graph using Hierholzer's algorithm.
+    /// Every edge, parallel ones included, is used once, and each of them ends where the next one
+    /// starts. A circuit is returned when the graph has one. Empty for a graph without edges.
+    /// O(V + E)
+    ///
+    pub fn path<'a, W, G>(graph: &'a G) -> Option<Vec<Edge>>
+        where G: EdgeIndexable<'a, Node, Edge, W> + NodeIndexable<Node> {
+        Euler::trail(graph, false)
+    }
+
+    // Eulerian circuit if `closed`, otherwise an Eulerian path
+    fn trail<'a, W, G>(graph: &'a G, closed: bool) -> Option<Vec<Edge>>
+        where G: EdgeIndexable<'a, Node, Edge, W> + NodeIndexable<Node> {
+
+        let Some(start) = Euler::start(graph, closed)? else {
+            return Some(Vec::new());
+        };
+
+        // Edges left to try from every node, an undirected edge is used through either copy
+        let mut edges: Vec<Vec<(Edge, usize)>> = (0..graph.node_count())
+            .map(|index| graph.get_edges(graph.node_at(index)).map(|(edge, neighbour, _)| (edge, graph.index_of(neighbour))).collect())
+            .collect();
+        let mut used = vec![false; graph.edge_count()];
+
+        // Walk until stuck, then back up adding edges to the trail in reverse
+        let mut trail = Vec::with_capacity(graph.edge_count());
+        let mut stack: Vec<(usize, Option<Edge>)> = vec![(start, None)];
+        while let Some(&(node, incoming)) = stack.last() {
+            let next = std::iter::from_fn(|| edges[node].pop())
+                .find(|(edge, _)| !used[graph.edge_index(*edge)]);
+            match next {
+                Some((edge, neighbour)) => {
+                    used[graph.edge_index(edge)] = true;
+                    stack.push((neighbour, Some(edge)));
+                },
+                None => {
+                    stack.pop();
+                    trail.extend(incoming);
+                }
+            }
+        }
+
+        // Edges in other components are left over
+        if trail.len() < graph.edge_count() {
+            return None;
+        }
+        trail.reverse();
+        Some(trail)
+    }
+
+    // Node the trail has to start from, judging by degrees alone. Some(None) if there are no edges.
+    fn start<'a, W, G>(graph: &'a G, closed: bool) -> Option<Option<usize>>
+        where G: EdgeIndexable<'a, Node, Edge, W> + NodeIndexable<Node> {
+
+        // Outgoing less incoming edges for directed graphs, degree for undirected ones
+        let mut balance = vec![0isize; graph.node_count()];
+        let mut counted = vec![false; graph.edge_count()];
+        for index in 0..graph.node_count() {
+            for (edge, neighbour, _) in graph.get_edges(graph.node_at(index)) {
+                let edge_index = graph.edge_index(edge);
+                if graph.is_directed() {
+                    balance[index] += 1;
+                    balance[graph.index_of(neighbour)] -= 1;
+                } else if !counted[edge_index] {
+                    counted[edge_index] = true;
+                    balance[index] += 1;
+                    balance[graph.index_of(neighbour)] += 1;
+                }
+            }
+        }
+
+        let any_node = (0..graph.node_count()).find(|&index| graph.get_edges(graph.node_at(index)).next().is_some());
+        if graph.is_directed() {
+            if balance.iter().any(|balance| balance.abs() > 1) {
+                return None;
+            }
+            let starts: Vec<usize> = (0..graph.node_count()).filter(|&index| balance[index] == 1).collect();
+            let end_count = balance.iter().filter(|&&balance| balance == -1).count();
+            match (starts.as_slice(), end_count) {
+                ([], 0) => Some(any_node),
+                ([start], 1) if !closed => Some(Some(*start)),
+                _ => None
+            }
+        } else {
+            let odd: Vec<usize> = (0..graph.node_count()).filter(|&index| balance[index] % 2 == 1).collect();
+            match odd.as_slice() {
+                [] => Some(any_node),
+                [start, _] if !closed => Some(Some(*start)),
+                _ => None
+            }
+        }
+    }
+}
@@ -0,0 +1,358 @@
+use std::collections::VecDeque;
+use std::ops::Sub;
+
+use crate::node_map::{NodeMap, EdgeMap};
+use crate::path_finder::{PathFinder, NodeIndexable, EdgeIndexable, DenseSearchState, SearchState};
+use crate::weighted_graph::{Node, Edge};
+
+pub struct MaxFlow;
+
+pub struct MinCostFlow;
+
+// Maximum flow between two nodes together with a minimum cut of the same value
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlowResult<W> {
+    pub value: W,
+
+    // Flow sent along every edge, never more than its capacity
+    pub flow: EdgeMap<W>,
+
+    // True for nodes on the source side of the minimum cut, the ones still reachable
+    // from the source in the residual graph
+    pub source_side: NodeMap<bool>,
+
+    // Edges leading from the source side to the other one, all of them saturated
+    pub cut_edges: Vec<Edge>
+}
+
+// Flow of the lowest total cost, where every edge has a (capacity, cost) pair as its weight
+#[derive(Debug, Clone, PartialEq)]
+pub struct CostFlowResult<W> {
+    pub value: W,
+
+    // Sum of flow times cost over all edges
+    pub cost: W,
+
+    // Flow sent along every edge, never more than its capacity
+    pub flow: EdgeMap<W>
+}
+
+// Residual graph with two arcs for every edge: arc 2i follows edge i and arc 2i + 1 goes back
+struct Residual<W> {
+    targets: Vec<usize>,
+    capacities: Vec<W>,
+    arcs: Vec<Vec<usize>>
+}
+
+impl<W: Copy + Ord + num_traits::Zero + Sub<Output = W>> Residual<W> {
+    // Lists every edge of a directed graph, `capacity` gets the index and the weight of an edge.
+    // Also returns edges by their index.
+    fn build<'a, Weight, G>(graph: &'a G, mut capacity: impl FnMut(usize, Weight) -> W) -> (Self, Vec<Option<Edge>>)
+        where G: EdgeIndexable<'a, Node, Edge, Weight> + NodeIndexable<Node> {
+
+        assert!(graph.is_directed(), "Flows are only defined for directed graphs");
+
+        let mut edges: Vec<Option<Edge>> = vec![None; graph.edge_count()];
+        let mut residual = Residual {
+            targets: vec![0; 2 * graph.edge_count()],
+            capacities: vec![W::zero(); 2 * graph.edge_count()],
+            arcs: vec![Vec::new(); graph.node_count()]
+        };
+        for index in 0..graph.node_count() {
+            for (edge, neighbour, weight) in graph.get_edges(graph.node_at(index)) {
+                let edge_index = graph.edge_index(edge);
+                let neighbour = graph.index_of(neighbour);
+                edges[edge_index] = Some(edge);
+
+                let edge_capacity = capacity(edge_index, weight);
+                assert!(edge_capacity >= W::zero(), "Capacity can't be negative");
+
+                residual.targets[2 * edge_index] = neighbour;
+                residual.targets[2 * edge_index + 1] = index;
+                residual.capacities[2 * edge_index] = edge_capacity;
+                residual.arcs[index].push(2 * edge_index);
+                residual.arcs[neighbour].push(2 * edge_index + 1);
+            }
+        }
+        (residual, edges)
+    }
+
+    fn push(&mut self, arc: usize, amount: W) {
+        self.capacities[arc] = self.capacities[arc] - amount;
+        self.capacities[arc ^ 1] = self.capacities[arc ^ 1] + amount;
+    }
+
+    fn source_of(&self, arc: usize) -> usize {
+        self.targets[arc ^ 1]
+    }
+
+    // Number of arcs from the source to every node using only arcs with capacity left
+    fn levels(&self, source: usize) -> Vec<Option<usize>> {
+        let mut levels = vec![None; self.arcs.len()];
+        levels[source] = Some(0);
+        let mut queue = VecDeque::from([source]);
+        while let Some(index) = queue.pop_front() {
+            for arc in self.arcs[index].iter() {
+                let target = self.targets[*arc];
+                if levels[target].is_none() && self.capacities[*arc] > W::zero() {
+                    levels[target] = Some(levels[index].unwrap() + 1);
+                    queue.push_back(target);
+                }
+            }
+        }
+        levels
+    }
+}
+
+// Nodes of the residual graph are their own indices
+impl<W> NodeIndexable<usize> for Residual<W> {
+    fn node_count(&self) -> usize {
+        self.arcs.len()
+    }
+
+    fn index_of(&self, n: usize) -> usize {
+        n
+    }
+
+    fn node_at(&self, index: usize) -> usize {
+        index
+    }
+}
+
+impl MaxFlow {
+
+    ///
+    /// Finds the maximum flow from source to target using Dinic's algorithm. Edge weights
+    /// are capacities. Returns the flow of every edge and a minimum cut.
+    /// O(V^2 E)
+    ///
+    /// # Panics
+    /// Panics if the graph is undirected, if any capacity is negative
+    /// or if source and target are the same node.
+    ///
+    pub fn dinic<'a, W, G>(graph: &'a G, source: Node, target: Node) -> FlowResult<W>
+        where G: EdgeIndexable<'a, Node, Edge, W> + NodeIndexable<Node>, W: Copy + Ord + num_traits::Zero + Sub<Output = W> {
+
+        assert!(source != target, "Source and target have to be different nodes");
+
+        let (mut residual, edges) = Residual::build(graph, |_, capacity| capacity);
+        let capacities = residual.capacities.clone();
+
+        let (source, target) = (graph.index_of(source), graph.index_of(target));
+        let mut value = W::zero();
+        let mut levels = residual.levels(source);
+        while levels[target].is_some() {
+            value = value + MaxFlow::blocking_flow(&mut residual, &mut levels, source, target);
+            levels = residual.levels(source);
+        }
+
+        let source_side = levels.iter().map(|level| level.is_some()).collect::<Vec<bool>>();
+        let mut flow = Vec::with_capacity(edges.len());
+        let mut cut_edges = Vec::new();
+        for (edge_index, edge) in edges.into_iter().enumerate() {
+            let arc = 2 * edge_index;
+            flow.push(capacities[arc] - residual.capacities[arc]);
+            if source_side[residual.source_of(arc)] && !source_side[residual.targets[arc]] {
+                cut_edges.push(edge.expect("Edge is not listed by its source"));
+            }
+        }
+
+        FlowResult {
+            value,
+            flow: EdgeMap::from_vec(flow),
+            source_side: NodeMap::from_vec(source_side),
+            cut_edges
+        }
+    }
+
+    // Saturates every shortest path from source to target, returns the amount of flow sent.
+    // Walks paths iteratively, keeping the next arc to try for every node, and removes
+    // dead ends from the level graph.
+    fn blocking_flow<W>(residual: &mut Residual<W>, levels: &mut [Option<usize>], source: usize, target: usize) -> W
+        where W: Copy + Ord + num_traits::Zero + Sub<Output = W> {
+
+        let mut sent = W::zero();
+        let mut next_arc = vec![0; residual.arcs.len()];
+        let mut path: Vec<usize> = Vec::new();
+        let mut curr_node = source;
+
+        loop {
+            if curr_node == target {
+                let amount = path.iter().map(|arc| residual.capacities[*arc]).min().expect("Path to target is empty");
+                for arc in path.iter() {
+                    residual.push(*arc, amount);
+                }
+                sent = sent + amount;
+
+                // Continue from the tail of the first saturated arc
+                let saturated = path.iter().position(|arc| residual.capacities[*arc] == W::zero()).unwrap();
+                path.truncate(saturated);
+                curr_node = path.last().map_or(source, |arc| residual.targets[*arc]);
+                continue;
+            }
+
+            let arcs = &residual.arcs[curr_node];
+            while next_arc[curr_node] < arcs.len() {
+                let arc = arcs[next_arc[curr_node]];
+                let next_level = levels[curr_node].map(|level| level + 1);
+                if residual.capacities[arc] > W::zero() && levels[residual.targets[arc]] == next_level {
+                    break;
+                }
+                next_arc[curr_node] += 1;
+            }
+
+            if let Some(arc) = arcs.get(next_arc[curr_node]) {
+                path.push(*arc);
+                curr_node = residual.targets[*arc];
+                continue;
+            }
+
+            // Dead end, nothing more can pass through this node
+            let Some(arc) = path.pop() else {
+                return sent;
+            };
+            levels[curr_node] = None;
+            curr_node = residual.source_of(arc);
+            next_arc[curr_node] += 1;
+        }
+    }
+}
+
+impl MinCostFlow {
+
+    ///
+    /// Finds the maximum flow from source to target with the lowest total cost.
+    /// Edge weights are (capacity, cost) pairs, costs may be negative.
+    /// Uses successive shortest paths with potentials, so one path costs a single Dijkstra.
+    /// O(F E log V), where F is the number of augmenting paths
+    ///
+    /// # Panics
+    /// Panics if the graph is undirected, if any capacity is negative, if source and target
+    /// are the same node or if edges with capacity form a cycle of negative cost.
+    ///
+    pub fn min_cost_max_flow<'a, W, G>(graph: &'a G, source: Node, target: Node) -> CostFlowResult<W>
+        where G: EdgeIndexable<'a, Node, Edge, (W, W)> + NodeIndexable<Node>, W: num_traits::Signed + Copy + Ord {
+
+        MinCostFlow::successive_shortest_paths(graph, source, target, None)
+    }
+
+    ///
+    /// Sends the given amount of flow from source to target with the lowest total cost.
+    /// Returns None if the maximum flow is lower than the amount.
+    /// Edge weights are (capacity, cost) pairs, costs may be negative.
+    /// O(F E log V), where F is the number of augmenting paths
+    ///
+    /// # Panics
+    /// Panics if the graph is undirected, if any capacity or the amount is negative, if source
+    /// and target are the same node or if edges with capacity form a cycle of negative cost.
+    ///
+    pub fn min_cost_flow<'a, W, G>(graph: &'a G, source: Node, target: Node, amount: W) -> Option<CostFlowResult<W>>
+        where G: EdgeIndexable<'a, Node, Edge, (W, W)> + NodeIndexable<Node>, W: num_traits::Signed + Copy + Ord {
+
+        assert!(amount >= W::zero(), "Amount of flow can't be negative");
+        let result = MinCostFlow::successive_shortest_paths(graph, source, target, Some(amount));
+        (result.value == amount).then_some(result)
+    }
+
+    // Sends flow along cheapest paths of the residual graph until the limit is reached
+    // or the target can't be reached anymore
+    fn successive_shortest_paths<'a, W, G>(graph: &'a G, source: Node, target: Node, limit: Option<W>) -> CostFlowResult<W>
+        where G: EdgeIndexable<'a, Node, Edge, (W, W)> + NodeIndexable<Node>, W: num_traits::Signed + Copy + Ord {
+
+        assert!(source != target, "Source and target have to be different nodes");
+
+        let mut costs = vec![W::zero(); 2 * graph.edge_count()];
+        let (mut residual, edges) = Residual::build(graph, |edge_index, (capacity, cost)| {
+            costs[2 * edge_index] = cost;
+            costs[2 * edge_index + 1] = -cost;
+            capacity
+        });
+        let capacities = residual.capacities.clone();
+
+        let (source, target) = (graph.index_of(source), graph.index_of(target));
+        let mut potentials = MinCostFlow::initial_potentials(&residual, &costs, source);
+        let mut value = W::zero();
+        let mut cost = W::zero();
+
+        while limit.is_none_or(|limit| value < limit) {
+            let Some(path) = MinCostFlow::cheapest_path(&residual, &costs, &mut potentials, source, target) else {
+                break;
+            };
+
+            let mut amount = path.iter().map(|arc| residual.capacities[*arc]).min().expect("Path to target is empty");
+            if let Some(limit) = limit {
+                amount = amount.min(limit - value);
+            }
+            for arc in path.iter() {
+                residual.push(*arc, amount);
+                cost = cost + amount * costs[*arc];
+            }
+            value = value + amount;
+        }
+
+        let flow = (0..edges.len())
+            .map(|edge_index| capacities[2 * edge_index] - residual.capacities[2 * edge_index])
+            .collect();
+        CostFlowResult { value, cost, flow: EdgeMap::from_vec(flow) }
+    }
+
+    // Distances from the source with Bellman-Ford, so that reduced costs of arcs with capacity
+    // are never negative. Skipped if no cost is negative.
+    fn initial_potentials<W: num_traits::Signed + Copy + Ord>(residual: &Residual<W>, costs: &[W], source: usize) -> Vec<W> {
+        let node_count = residual.arcs.len();
+        if costs.iter().step_by(2).all(|cost| *cost >= W::zero()) {
+            return vec![W::zero(); node_count];
+        }
+
+        let mut distances: Vec<Option<W>> = vec![None; node_count];
+        distances[source] = Some(W::zero());
+        for round in 0..node_count {
+            let mut changed = false;
+            for arc in 0..costs.len() {
+                let Some(distance) = distances[residual.source_of(arc)] else {
+                    continue;
+                };
+                let target = residual.targets[arc];
+                let through_arc = distance + costs[arc];
+                if residual.capacities[arc] > W::zero() && distances[target].is_none_or(|current| through_arc < current) {
+                    distances[target] = Some(through_arc);
+                    changed = true;
+                }
+            }
+            if !changed {
+                break;
+            }
+            assert!(round + 1 < node_count, "Graph contains a cycle of negative cost");
+        }
+
+        // Nodes out of reach now stay out of reach, their potential doesn't matter
+        distances.into_iter().map(|distance| distance.unwrap_or(W::zero())).collect()
+    }
+
+    // Dijkstra over reduced costs, returns arcs of the cheapest path and updates potentials
+    fn cheapest_path<W: num_traits::Signed + Copy + Ord>(residual: &Residual<W>, costs: &[W], potentials: &mut [W], source: usize, target: usize) -> Option<Vec<usize>> {
+        let reduced_cost = |arc: usize| costs[arc] + potentials[residual.source_of(arc)] - potentials[residual.targets[arc]];
+        let neighbours = |index: usize| residual.arcs[index].iter()
+            .filter(|arc| residual.capacities[**arc] > W::zero())
+            .map(|arc| (residual.targets[*arc], reduced_cost(*arc)));
+
+        let mut state = DenseSearchState::new(residual);
+        let (nodes, target_distance) = PathFinder::dijkstra(neighbours, source, target, W::zero(), |distance, cost| distance + cost, &mut state)?;
+
+        // Of parallel arcs the search went along the cheapest one
+        let path = nodes.windows(2)
+            .map(|pair| residual.arcs[pair[0]].iter().copied()
+                .filter(|arc| residual.targets[*arc] == pair[1] && residual.capacities[*arc] > W::zero())
+                .min_by_key(|arc| reduced_cost(*arc))
+                .expect("Path follows arcs with capacity"))
+            .collect();
+
+        // The search stops at the target, so every node closer than it is settled
+        // and the rest is treated as being as far as the target
+        for (index, potential) in potentials.iter_mut().enumerate() {
+            let distance = state.distance(index).map_or(target_distance, |distance| distance.min(target_distance));
+            *potential = *potential + distance;
+        }
+        Some(path)
+    }
+}
@@ -3,13 +3,14 @@ use std::collections::hash_map::Entry::*;
 use std::ops::{IndexMut, Index};
 use std::{fmt, hash::Hash};
 use crate::graph_trait::*;
-use crate::path_finder::PathFindable;
+use crate::path_finder::{PathFindable, NodeIndexable, EdgeIndexable};
 use crate::weighted_graph::*;
 
 pub type Node = crate::weighted_graph::Node;
 pub type Edge = crate::weighted_graph::Edge;
 pub type NodeIter<'a, T> = crate::weighted_graph::NodeIter<'a, T>;
 
+#[derive(Clone)]
 struct EmptyWeight;
 
 pub struct Graph<T> {
@@ -44,6 +45,11 @@ impl<T> Graph<T> {
         self.graph.len()
     }
 
+    // Number of edges, an undirected edge counts once
+    pub fn edge_count(&self) -> usize {
+        self.graph.edge_count()
+    }
+
     // Get a vector of neighbouring nodes
     pub fn get_neighbours(&self, node: Node) -> EdgeIter {
         EdgeIter { iterator: self.graph.get_neighbours(node) }
@@ -52,6 +58,19 @@ impl<T> Graph<T> {
     pub fn get_degree(&self, node: Node) -> usize {
         self.graph.get_degree(node)
     }
+
+    pub fn is_directed(&self) -> bool {
+        self.graph.is_directed()
+    }
+}
+
+impl<T: Clone> Graph<T> {
+    // Copy of the given nodes and of the edges between them, nodes keep the given order.
+    // The copy uses the built-in directed or undirected storage.
+    // O(V + E)
+    pub fn induced_subgraph(&self, nodes: &[Node]) -> Self {
+        Graph { graph: self.graph.induced_subgraph(nodes) }
+    }
 }
 
 impl<T: Copy> Graph<T> {
@@ -207,4 +226,36 @@ impl<'a, T> PathFindable<'a, Node, usize> for Graph<T> {
     fn get_neighbours(&'a self, n: Node) -> Box<dyn Iterator<Item=(Node, usize)> + 'a> {
         Box::new(self.get_neighbours(n).map(|edge| (edge.target, 1)))
     }
-}
\ No newline at end of file
+}
+
+impl<T> NodeIndexable<Node> for Graph<T> {
+    fn node_count(&self) -> usize {
+        self.len()
+    }
+
+    fn index_of(&self, n: Node) -> usize {
+        self.graph.index_of(n)
+    }
+
+    fn node_at(&self, index: usize) -> Node {
+        self.graph.node_at(index)
+    }
+}
+
+impl<'a, T> EdgeIndexable<'a, Node, Edge, usize> for Graph<T> {
+    fn edge_count(&self) -> usize {
+        self.edge_count()
+    }
+
+    fn edge_index(&self, e: Edge) -> usize {
+        e.uid
+    }
+
+    fn is_directed(&self) -> bool {
+        self.is_directed()
+    }
+
+    fn get_edges(&'a self, n: Node) -> Box<dyn Iterator<Item=(Edge, Node, usize)> + 'a> {
+        Box::new(self.get_neighbours(n).map(|edge| (edge, edge.target, 1)))
+    }
+}
@@ -39,5 +39,10 @@ pub trait GraphType {
     // Get a vector of neighbouring nodes
     fn get_neighbours(&self, node: GraphNode) -> GraphEdgeIterator;
 
+    // Whether edges only lead from source to target. Undirected storages return false.
+    fn is_directed(&self) -> bool {
+        true
+    }
+
     fn new() -> Self where Self:Sized;
 }
\ No newline at end of file
@@ -2,9 +2,27 @@ mod priority_node;
 mod directed;
 mod undirected;
 mod adjacency_list;
+mod weighted_blossom;
+mod rng;
 
 pub mod graph_trait;
 pub mod graph;
 //pub mod graph_utils;
 pub mod weighted_graph;
-pub mod path_finder;
\ No newline at end of file
+pub mod path_finder;
+pub mod traversal;
+pub mod topological_sort;
+pub mod node_map;
+pub mod connectivity;
+pub mod union_find;
+pub mod spanning_tree;
+pub mod flow;
+pub mod matching;
+pub mod centrality;
+pub mod clustering;
+pub mod community;
+pub mod cores;
+pub mod cliques;
+pub mod colouring;
+pub mod cycles;
+pub mod euler;
\ No newline at end of file
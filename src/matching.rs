@@ -0,0 +1,566 @@
+use std::collections::VecDeque;
+use std::fmt;
+
+use crate::node_map::NodeMap;
+use crate::path_finder::{NodeIndexable, EdgeIndexable};
+use crate::union_find::UnionFind;
+use crate::weighted_blossom::WeightedBlossom;
+use crate::weighted_graph::{Node, Edge};
+
+pub struct Matching;
+
+// Set of edges without common nodes
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchingResult {
+    // One copy of every matched edge
+    pub edges: Vec<Edge>,
+
+    // Node matched with every node, if any
+    pub mate: NodeMap<Option<Node>>
+}
+
+// Split of nodes into two sides, every edge connects nodes of different sides.
+// The first node of every connected component is on the left side.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bipartition {
+    pub left: Vec<Node>,
+    pub right: Vec<Node>,
+
+    // True for nodes on the left side
+    pub is_left: NodeMap<bool>
+}
+
+// Returned when the graph is not bipartite. Every node of the cycle is connected to the next
+// one, and the last node to the first. Edge directions are ignored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OddCycleError<Node> {
+    pub cycle: Vec<Node>
+}
+
+impl<Node: fmt::Debug> fmt::Display for OddCycleError<Node> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "graph contains an odd cycle: {:?}", self.cycle)
+    }
+}
+
+impl<Node: fmt::Debug> std::error::Error for OddCycleError<Node> {}
+
+impl Matching {
+
+    ///
+    /// Splits nodes into two sides with no edge inside either of them, using BFS.
+    /// Edge directions are ignored. Fails with one of the odd cycles if the graph has any.
+    /// O(V + E)
+    ///
+    pub fn bipartition<'a, Dist, G>(graph: &'a G) -> Result<Bipartition, OddCycleError<Node>>
+        where G: EdgeIndexable<'a, Node, Edge, Dist> + NodeIndexable<Node> {
+
+        let adjacency = Matching::undirected_adjacency(graph);
+        let mut is_left: Vec<Option<bool>> = vec![None; graph.node_count()];
+        let mut parent: Vec<Option<usize>> = vec![None; graph.node_count()];
+
+        for root in 0..graph.node_count() {
+            if is_left[root].is_some() {
+                continue;
+            }
+            is_left[root] = Some(true);
+            let mut queue = VecDeque::from([root]);
+
+            while let Some(index) = queue.pop_front() {
+                for (neighbour, _) in adjacency[index].iter() {
+                    match is_left[*neighbour] {
+                        None => {
+                            is_left[*neighbour] = is_left[index].map(|side| !side);
+                            parent[*neighbour] = Some(index);
+                            queue.push_back(*neighbour);
+                        },
+                        Some(side) if Some(side) == is_left[index] => {
+                            let cycle = Matching::odd_cycle(&parent, index, *neighbour);
+                            return Err(OddCycleError { cycle: cycle.into_iter().map(|index| graph.node_at(index)).collect() });
+                        },
+                        _ => ()
+                    }
+                }
+            }
+        }
+
+        let is_left = is_left.into_iter().map(|side| side.unwrap()).collect::<Vec<bool>>();
+        let (left, right): (Vec<Node>, Vec<Node>) = (0..graph.node_count())
+            .map(|index| graph.node_at(index))
+            .partition(|node| is_left[graph.index_of(*node)]);
+        Ok(Bipartition { left, right, is_left: NodeMap::from_vec(is_left) })
+    }
+
+    ///
+    /// Finds a maximum cardinality matching of a bipartite graph using Hopcroft-Karp.
+    /// Edge directions and weights are ignored.
+    /// O(E sqrt(V))
+    ///
+    /// # Panics
+    /// Panics if the graph is not bipartite.
+    ///
+    pub fn hopcroft_karp<'a, Dist, G>(graph: &'a G) -> MatchingResult
+        where G: EdgeIndexable<'a, Node, Edge, Dist> + NodeIndexable<Node> {
+
+        let bipartition = Matching::bipartition(graph).expect("Graph is not bipartite");
+        let adjacency = Matching::undirected_adjacency(graph);
+        let left = bipartition.left.iter().map(|node| graph.index_of(*node)).collect::<Vec<usize>>();
+
+        // Mate of every node with the matched edge
+        let mut mate: Vec<Option<(usize, Edge)>> = vec![None; graph.node_count()];
+
+        // Every phase augments along a maximal set of shortest disjoint paths
+        loop {
+            // Length of the shortest alternating path from a free left node to every left node
+            let mut distance: Vec<Option<usize>> = vec![None; graph.node_count()];
+            let mut queue = VecDeque::new();
+            for index in left.iter() {
+                if mate[*index].is_none() {
+                    distance[*index] = Some(0);
+                    queue.push_back(*index);
+                }
+            }
+
+            let mut found_free = false;
+            while let Some(index) = queue.pop_front() {
+                for (neighbour, _) in adjacency[index].iter() {
+                    match mate[*neighbour] {
+                        None => found_free = true,
+                        Some((next, _)) if distance[next].is_none() => {
+                            distance[next] = distance[index].map(|distance| distance + 1);
+                            queue.push_back(next);
+                        },
+                        _ => ()
+                    }
+                }
+            }
+            if !found_free {
+                break;
+            }
+
+            let mut next_edge = vec![0; graph.node_count()];
+            for start in left.iter() {
+                if mate[*start].is_none() {
+                    Matching::augment(&adjacency, &mut mate, &mut distance, &mut next_edge, *start);
+                }
+            }
+        }
+
+        Matching::collect(graph, mate)
+    }
+
+    ///
+    /// Solves the assignment problem with the Hungarian algorithm: matches every node of the smaller
+    /// side of every connected component of a bipartite graph (the left one if both are equal)
+    /// with the lowest total weight. Edge directions are ignored and of parallel edges only the lightest counts.
+    /// Returns None if there is no such assignment.
+    /// O(V^3)
+    ///
+    /// # Panics
+    /// Panics if the graph is not bipartite.
+    ///
+    pub fn hungarian<'a, W, G>(graph: &'a G) -> Option<(MatchingResult, W)>
+        where G: EdgeIndexable<'a, Node, Edge, W> + NodeIndexable<Node>, W: num_traits::Signed + Copy + Ord {
+
+        let bipartition = Matching::bipartition(graph).expect("Graph is not bipartite");
+
+        // Sides are chosen independently in every component, so each of them is oriented on its own
+        let mut sets = UnionFind::new(graph.node_count());
+        for index in 0..graph.node_count() {
+            for (_, neighbour, _) in graph.get_edges(graph.node_at(index)) {
+                sets.union(index, graph.index_of(neighbour));
+            }
+        }
+        let roots: Vec<usize> = (0..graph.node_count()).map(|index| sets.find(index)).collect();
+
+        // Left less right nodes of every component, by its root
+        let mut balance = vec![0isize; graph.node_count()];
+        for index in 0..graph.node_count() {
+            balance[roots[index]] += if bipartition.is_left[graph.node_at(index)] { 1 } else { -1 };
+        }
+        let (rows, columns): (Vec<Node>, Vec<Node>) = (0..graph.node_count())
+            .map(|index| graph.node_at(index))
+            .partition(|node| bipartition.is_left[*node] == (balance[roots[graph.index_of(*node)]] <= 0));
+
+        // Position of every node on its side
+        let mut position = vec![0; graph.node_count()];
+        for side in [&rows, &columns] {
+            for (index, node) in side.iter().enumerate() {
+                position[graph.index_of(*node)] = index;
+            }
+        }
+
+        // Lightest edge between every row and column
+        let mut costs: Vec<Vec<Option<(W, Edge)>>> = vec![vec![None; columns.len()]; rows.len()];
+        for (row, node) in rows.iter().enumerate() {
+            for (edge, neighbour, weight) in graph.get_edges(*node) {
+                let column = position[graph.index_of(neighbour)];
+                if costs[row][column].is_none_or(|(current, _)| weight < current) {
+                    costs[row][column] = Some((weight, edge));
+                }
+            }
+        }
+        // Edges of a directed graph can lead from columns to rows
+        if graph.is_directed() {
+            for (column, node) in columns.iter().enumerate() {
+                for (edge, neighbour, weight) in graph.get_edges(*node) {
+                    let row = position[graph.index_of(neighbour)];
+                    if costs[row][column].is_none_or(|(current, _)| weight < current) {
+                        costs[row][column] = Some((weight, edge));
+                    }
+                }
+            }
+        }
+
+        let assigned_row = Matching::assign(&costs, rows.len(), columns.len())?;
+
+        let mut mate: Vec<Option<(usize, Edge)>> = vec![None; graph.node_count()];
+        let mut total = W::zero();
+        for (column, row) in assigned_row.into_iter().enumerate() {
+            if let Some(row) = row {
+                let (weight, edge) = costs[row][column].expect("Assigned pair has no edge");
+                let (row_index, column_index) = (graph.index_of(rows[row]), graph.index_of(columns[column]));
+                mate[row_index] = Some((column_index, edge));
+                mate[column_index] = Some((row_index, edge));
+                total = total + weight;
+            }
+        }
+        Some((Matching::collect(graph, mate), total))
+    }
+
+    ///
+    /// Finds a maximum cardinality matching of any graph using Edmonds' blossom algorithm.
+    /// Edge directions and weights are ignored, self-loops never match.
+    /// O(V^3)
+    ///
+    pub fn edmonds<'a, Dist, G>(graph: &'a G) -> MatchingResult
+        where G: EdgeIndexable<'a, Node, Edge, Dist> + NodeIndexable<Node> {
+
+        let adjacency = Matching::undirected_adjacency(graph);
+        let mut mate: Vec<Option<(usize, Edge)>> = vec![None; graph.node_count()];
+
+        // Greedy matching first, the search only has to improve on it
+        for index in 0..graph.node_count() {
+            if mate[index].is_some() {
+                continue;
+            }
+            if let Some((neighbour, edge)) = adjacency[index].iter().find(|(neighbour, _)| *neighbour != index && mate[*neighbour].is_none()) {
+                mate[index] = Some((*neighbour, *edge));
+                mate[*neighbour] = Some((index, *edge));
+            }
+        }
+
+        let mut search = BlossomSearch::new(graph.node_count());
+        for root in 0..graph.node_count() {
+            if mate[root].is_some() {
+                continue;
+            }
+
+            // Flip the alternating path ending at the free node that was found
+            let mut curr_node = search.find_augmenting_path(&adjacency, &mate, root);
+            while let Some(index) = curr_node {
+                let (parent, edge) = search.parent[index].expect("Augmenting path is broken");
+                curr_node = mate[parent].map(|(next, _)| next);
+                mate[index] = Some((parent, edge));
+                mate[parent] = Some((index, edge));
+            }
+        }
+
+        Matching::collect(graph, mate)
+    }
+
+    ///
+    /// Finds a matching of any graph with the highest total weight using Edmonds' weighted
+    /// blossom algorithm. Edges with negative weight never match, self-loops neither.
+    /// The matching doesn't have to be of maximum cardinality. Edge directions are ignored.
+    /// O(V^3)
+    ///
+    pub fn max_weight_matching<'a, W, G>(graph: &'a G) -> (MatchingResult, W)
+        where G: EdgeIndexable<'a, Node, Edge, W> + NodeIndexable<Node>, W: num_traits::Signed + Copy + Ord {
+
+        // Every edge once, with node indices of its ends
+        let mut listed = vec![false; graph.edge_count()];
+        let mut edges = Vec::new();
+        let mut weighted_edges = Vec::new();
+        for index in 0..graph.node_count() {
+            for (edge, neighbour, weight) in graph.get_edges(graph.node_at(index)) {
+                let neighbour = graph.index_of(neighbour);
+                if neighbour != index && !listed[graph.edge_index(edge)] {
+                    listed[graph.edge_index(edge)] = true;
+                    edges.push(edge);
+                    weighted_edges.push((index, neighbour, weight));
+                }
+            }
+        }
+
+        let mate = WeightedBlossom::new(graph.node_count(), weighted_edges.clone()).solve();
+
+        let mut total = W::zero();
+        let mate = mate.into_iter()
+            .enumerate()
+            .map(|(index, matched)| matched.map(|position| {
+                let (a, b, weight) = weighted_edges[position];
+                if index == a {
+                    total = total + weight;
+                }
+                (if index == a { b } else { a }, edges[position])
+            }))
+            .collect();
+        (Matching::collect(graph, mate), total)
+    }
+
+    // Hungarian algorithm with potentials over a rows x columns cost matrix, rows <= columns.
+    // Returns the row assigned to every column, or None if some row can't be assigned.
+    fn assign<W: num_traits::Signed + Copy + Ord>(costs: &[Vec<Option<(W, Edge)>>], rows: usize, columns: usize) -> Option<Vec<Option<usize>>> {
+        // Column 0 is a dummy one holding the row being assigned, so rows and columns are counted from 1
+        let mut row_potential = vec![W::zero(); rows + 1];
+        let mut column_potential = vec![W::zero(); columns + 1];
+        let mut assigned_row = vec![0; columns + 1];
+        let mut way = vec![0; columns + 1];
+
+        for row in 1..=rows {
+            assigned_row[0] = row;
+            let mut curr_column = 0;
+            let mut min_slack: Vec<Option<W>> = vec![None; columns + 1];
+            let mut used = vec![false; columns + 1];
+
+            // Grow the alternating tree until it reaches a free column
+            loop {
+                used[curr_column] = true;
+                let curr_row = assigned_row[curr_column];
+                let mut delta: Option<(W, usize)> = None;
+                for column in 1..=columns {
+                    if used[column] {
+                        continue;
+                    }
+                    if let Some((cost, _)) = costs[curr_row - 1][column - 1] {
+                        let slack = cost - row_potential[curr_row] - column_potential[column];
+                        if min_slack[column].is_none_or(|current| slack < current) {
+                            min_slack[column] = Some(slack);
+                            way[column] = curr_column;
+                        }
+                    }
+                    if let Some(slack) = min_slack[column] {
+                        if delta.is_none_or(|(delta, _)| slack < delta) {
+                            delta = Some((slack, column));
+                        }
+                    }
+                }
+
+                let (delta, next_column) = delta?;
+                for column in 0..=columns {
+                    if used[column] {
+                        row_potential[assigned_row[column]] = row_potential[assigned_row[column]] + delta;
+                        column_potential[column] = column_potential[column] - delta;
+                    } else if let Some(slack) = min_slack[column].as_mut() {
+                        *slack = *slack - delta;
+                    }
+                }
+
+                curr_column = next_column;
+                if assigned_row[curr_column] == 0 {
+                    break;
+                }
+            }
+
+            // Flip the alternating path back to the dummy column
+            while curr_column != 0 {
+                let previous_column = way[curr_column];
+                assigned_row[curr_column] = assigned_row[previous_column];
+                curr_column = previous_column;
+            }
+        }
+
+        Some(assigned_row.into_iter().skip(1).map(|row| row.checked_sub(1)).collect())
+    }
+
+    // Looks for an augmenting path from a free left node along the layers found by BFS.
+    // Walks iteratively, a left node without a way forward is removed from the layers.
+    fn augment(adjacency: &[Vec<(usize, Edge)>], mate: &mut [Option<(usize, Edge)>], distance: &mut [Option<usize>], next_edge: &mut [usize], start: usize) {
+        let mut path = vec![start];
+        while let Some(index) = path.last().copied() {
+            let Some((neighbour, _)) = adjacency[index].get(next_edge[index]).copied() else {
+                // Dead end
+                distance[index] = None;
+                path.pop();
+                if let Some(previous) = path.last() {
+                    next_edge[*previous] += 1;
+                }
+                continue;
+            };
+
+            match mate[neighbour] {
+                None => {
+                    // Free right node, flip the path. Every left node of the path uses the edge it's at.
+                    for left in path.iter().rev() {
+                        let (right, edge) = adjacency[*left][next_edge[*left]];
+                        mate[*left] = Some((right, edge));
+                        mate[right] = Some((*left, edge));
+                    }
+                    return;
+                },
+                Some((next, _)) if distance[next].is_some() && distance[next] == distance[index].map(|distance| distance + 1) => {
+                    path.push(next);
+                },
+                _ => next_edge[index] += 1
+            }
+        }
+    }
+
+    // Neighbours of every node with the edges leading to them, in both directions of directed edges
+    fn undirected_adjacency<'a, Dist, G>(graph: &'a G) -> Vec<Vec<(usize, Edge)>>
+        where G: EdgeIndexable<'a, Node, Edge, Dist> + NodeIndexable<Node> {
+
+        let mut adjacency = vec![Vec::new(); graph.node_count()];
+        for index in 0..graph.node_count() {
+            for (edge, neighbour, _) in graph.get_edges(graph.node_at(index)) {
+                let neighbour = graph.index_of(neighbour);
+                adjacency[index].push((neighbour, edge));
+                if graph.is_directed() {
+                    adjacency[neighbour].push((index, edge));
+                }
+            }
+        }
+        adjacency
+    }
+
+    // Cycle closed by an edge between two nodes of the same BFS layer parity,
+    // through their lowest common ancestor in the BFS tree
+    fn odd_cycle(parent: &[Option<usize>], a: usize, b: usize) -> Vec<usize> {
+        let ancestors = |mut index: usize| {
+            let mut ancestors = vec![index];
+            while let Some(next) = parent[index] {
+                ancestors.push(next);
+                index = next;
+            }
+            ancestors
+        };
+        let (mut a_path, mut b_path) = (ancestors(a), ancestors(b));
+
+        // Drop the common part above the lowest common ancestor
+        while a_path.len() > 1 && b_path.len() > 1 && a_path[a_path.len() - 2] == b_path[b_path.len() - 2] {
+            a_path.pop();
+            b_path.pop();
+        }
+        b_path.pop();
+        b_path.reverse();
+        a_path.extend(b_path);
+        a_path
+    }
+
+    fn collect<'a, Dist, G>(graph: &'a G, mate: Vec<Option<(usize, Edge)>>) -> MatchingResult
+        where G: EdgeIndexable<'a, Node, Edge, Dist> + NodeIndexable<Node> {
+
+        let edges = mate.iter()
+            .enumerate()
+            .filter_map(|(index, mate)| mate.filter(|(other, _)| index < *other).map(|(_, edge)| edge))
+            .collect();
+        let mate = mate.into_iter().map(|mate| mate.map(|(other, _)| graph.node_at(other))).collect();
+        MatchingResult { edges, mate: NodeMap::from_vec(mate) }
+    }
+}
+
+// State of one search for an augmenting path in Edmonds' algorithm. Blossoms are contracted
+// by pointing every node of them at a common base.
+struct BlossomSearch {
+    // Node and edge every node was reached from, for odd nodes of the alternating tree
+    parent: Vec<Option<(usize, Edge)>>,
+    base: Vec<usize>,
+    in_tree: Vec<bool>,
+    in_blossom: Vec<bool>,
+    queue: VecDeque<usize>
+}
+
+impl BlossomSearch {
+    fn new(node_count: usize) -> Self {
+        BlossomSearch {
+            parent: vec![None; node_count],
+            base: (0..node_count).collect(),
+            in_tree: vec![false; node_count],
+            in_blossom: vec![false; node_count],
+            queue: VecDeque::new()
+        }
+    }
+
+    // Grows an alternating tree from a free root with BFS, returns the free node it reached
+    fn find_augmenting_path(&mut self, adjacency: &[Vec<(usize, Edge)>], mate: &[Option<(usize, Edge)>], root: usize) -> Option<usize> {
+        self.parent.fill(None);
+        self.in_tree.fill(false);
+        for (index, base) in self.base.iter_mut().enumerate() {
+            *base = index;
+        }
+        self.queue.clear();
+
+        self.in_tree[root] = true;
+        self.queue.push_back(root);
+        while let Some(index) = self.queue.pop_front() {
+            for (neighbour, edge) in adjacency[index].iter() {
+                let neighbour = *neighbour;
+                let mate_of_neighbour = mate[neighbour].map(|(next, _)| next);
+                if self.base[index] == self.base[neighbour] || mate[index].map(|(next, _)| next) == Some(neighbour) {
+                    continue;
+                }
+
+                // Edge between two even nodes closes a blossom
+                if neighbour == root || mate_of_neighbour.is_some_and(|next| self.parent[next].is_some()) {
+                    let common_base = self.lowest_common_base(mate, index, neighbour);
+                    self.in_blossom.fill(false);
+                    self.mark_path(mate, index, common_base, (neighbour, *edge));
+                    self.mark_path(mate, neighbour, common_base, (index, *edge));
+                    for node in 0..self.base.len() {
+                        if self.in_blossom[self.base[node]] {
+                            self.base[node] = common_base;
+                            if !self.in_tree[node] {
+                                self.in_tree[node] = true;
+                                self.queue.push_back(node);
+                            }
+                        }
+                    }
+                } else if self.parent[neighbour].is_none() {
+                    self.parent[neighbour] = Some((index, *edge));
+                    let Some(next) = mate_of_neighbour else {
+                        return Some(neighbour);
+                    };
+                    self.in_tree[next] = true;
+                    self.queue.push_back(next);
+                }
+            }
+        }
+        None
+    }
+
+    // Base of the blossom where paths from both nodes to the root meet
+    fn lowest_common_base(&self, mate: &[Option<(usize, Edge)>], mut a: usize, mut b: usize) -> usize {
+        let mut on_path = vec![false; self.base.len()];
+        loop {
+            a = self.base[a];
+            on_path[a] = true;
+            let Some((next, _)) = mate[a] else {
+                break;
+            };
+            a = self.parent[next].expect("Matched node has no parent").0;
+        }
+        loop {
+            b = self.base[b];
+            if on_path[b] {
+                return b;
+            }
+            let (next, _) = mate[b].expect("Path to the root is broken");
+            b = self.parent[next].expect("Matched node has no parent").0;
+        }
+    }
+
+    // Marks blossom nodes on the path from the node to the base, so that odd nodes of the path
+    // can lead back through the edge that closed the blossom
+    fn mark_path(&mut self, mate: &[Option<(usize, Edge)>], mut index: usize, base: usize, mut child: (usize, Edge)) {
+        while self.base[index] != base {
+            let (next, _) = mate[index].expect("Blossom node is not matched");
+            self.in_blossom[self.base[index]] = true;
+            self.in_blossom[self.base[next]] = true;
+            let (grandparent, edge) = self.parent[next].expect("Matched node has no parent");
+            self.parent[index] = Some(child);
+            child = (next, edge);
+            index = grandparent;
+        }
+    }
+}
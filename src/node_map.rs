@@ -0,0 +1,97 @@
+use std::ops::{Index, IndexMut};
+
+use crate::weighted_graph::{Node, Edge};
+
+// Value for every node of a graph, as returned by graph algorithms.
+// Values are stored at the positions given by NodeIndexable::index_of.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeMap<V> {
+    values: Vec<V>
+}
+
+impl<V> NodeMap<V> {
+    pub(crate) fn from_vec(values: Vec<V>) -> Self {
+        NodeMap { values }
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    // Iterate over nodes with their values
+    pub fn iter(&self) -> impl Iterator<Item = (Node, &V)> {
+        self.values.iter().enumerate().map(|(index, value)| (Node::from(index), value))
+    }
+
+    // Values in order of node indices
+    pub fn values(&self) -> &[V] {
+        &self.values
+    }
+
+    pub fn into_vec(self) -> Vec<V> {
+        self.values
+    }
+}
+
+impl<V> Index<Node> for NodeMap<V> {
+    type Output = V;
+
+    fn index(&self, index: Node) -> &Self::Output {
+        &self.values[index.uid]
+    }
+}
+
+impl<V> IndexMut<Node> for NodeMap<V> {
+    fn index_mut(&mut self, index: Node) -> &mut Self::Output {
+        &mut self.values[index.uid]
+    }
+}
+
+// Value for every edge of a graph, as returned by graph algorithms.
+// Values are stored at the positions given by EdgeIndexable::edge_index, both copies
+// of an undirected edge share one value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EdgeMap<V> {
+    values: Vec<V>
+}
+
+impl<V> EdgeMap<V> {
+    pub(crate) fn from_vec(values: Vec<V>) -> Self {
+        EdgeMap { values }
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    // Values in order of edge indices, which is the order edges were added in
+    pub fn values(&self) -> &[V] {
+        &self.values
+    }
+
+    pub fn into_vec(self) -> Vec<V> {
+        self.values
+    }
+}
+
+impl<V> Index<Edge> for EdgeMap<V> {
+    type Output = V;
+
+    fn index(&self, index: Edge) -> &Self::Output {
+        &self.values[index.uid]
+    }
+}
+
+impl<V> IndexMut<Edge> for EdgeMap<V> {
+    fn index_mut(&mut self, index: Edge) -> &mut Self::Output {
+        &mut self.values[index.uid]
+    }
+}
@@ -1,4 +1,4 @@
-use std::{collections::HashMap, hash::Hash, fmt::Debug};
+use std::{collections::{BinaryHeap, HashMap, HashSet, VecDeque}, hash::Hash, fmt::Debug};
 use num_traits;
 
 use crate::priority_node;
@@ -13,110 +13,489 @@ pub trait PathFindable<'a, Node, Dist> {
     fn get_neighbours(&'a self, n: Node) -> Box<dyn Iterator<Item=(Node, Dist)> + 'a>;
 }
 
+// Optional capability of a PathFindable graph whose nodes map onto the dense range
+// 0..node_count(). It lets algorithms keep their state in vectors instead of hash maps.
+pub trait NodeIndexable<Node> {
+    fn node_count(&self) -> usize;
+
+    // Position of the node in 0..node_count()
+    fn index_of(&self, n: Node) -> usize;
+
+    // Node at the given position, inverse of index_of
+    fn node_at(&self, index: usize) -> Node;
+}
+
+// Optional capability of a PathFindable graph that exposes its edges. Edges map onto the dense
+// range 0..edge_count(), an undirected edge is listed by both of its nodes under the same index.
+pub trait EdgeIndexable<'a, Node, Edge, Dist> {
+    fn edge_count(&self) -> usize;
+
+    // Position of the edge in 0..edge_count()
+    fn edge_index(&self, e: Edge) -> usize;
+
+    fn is_directed(&self) -> bool;
+
+    // Iterator over edges leaving the node with the neighbour on their other end and their weight
+    fn get_edges(&'a self, n: Node) -> Box<dyn Iterator<Item=(Edge, Node, Dist)> + 'a>;
+}
+
+// Limits a path has to respect in `find_constrained_path`. Edge weights are arrays of N costs,
+// the first cost is the one being minimised and every one of them can be capped.
+pub struct PathConstraints<Node, Dist, const N: usize> {
+    // Maximum number of edges on the path
+    pub max_hops: Option<usize>,
+
+    // Maximum sum of each cost along the path
+    pub max_costs: [Option<Dist>; N],
+
+    pub forbidden_nodes: HashSet<Node>,
+
+    // Forbids every edge leading from the first node to the second one. Parallel edges between
+    // the nodes are all forbidden together, one of them can't be forbidden on its own.
+    // Undirected edges need both directions forbidden.
+    pub forbidden_edges: HashSet<(Node, Node)>
+}
+
+impl<Node, Dist: Copy, const N: usize> PathConstraints<Node, Dist, N> {
+    // No limits at all
+    pub fn new() -> Self {
+        PathConstraints { max_hops: None, max_costs: [None; N], forbidden_nodes: HashSet::new(), forbidden_edges: HashSet::new() }
+    }
+}
+
+impl<Node, Dist: Copy, const N: usize> Default for PathConstraints<Node, Dist, N> {
+    fn default() -> Self {
+        PathConstraints::new()
+    }
+}
+
+// Cost made of several criteria, compared by `find_pareto_paths`. The ordering picks the next
+// label to settle, so it has to agree with dominance (a dominating b implies a <= b),
+// which lexicographic ordering does.
+pub trait ParetoCost: Copy + Ord {
+    fn zero() -> Self;
+
+    fn add(&self, other: &Self) -> Self;
+
+    // True if no criterion of self is worse than the other's
+    fn dominates(&self, other: &Self) -> bool;
+}
+
+impl<Dist: num_traits::PrimInt, const N: usize> ParetoCost for [Dist; N] {
+    fn zero() -> Self {
+        [Dist::zero(); N]
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        let mut sum = *self;
+        for (value, other_value) in sum.iter_mut().zip(other.iter()) {
+            *value = *value + *other_value;
+        }
+        sum
+    }
+
+    fn dominates(&self, other: &Self) -> bool {
+        self.iter().zip(other.iter()).all(|(value, other_value)| value <= other_value)
+    }
+}
+
 impl PathFinder {
 
     ///
     /// Finds shortest path between a and b, using Dijkstra algorightm.
     /// Returns None if there is no path between a and b.
     ///
+    /// State is kept in hash maps and only created for nodes the search reaches.
+    /// For graphs implementing NodeIndexable `find_shortest_path_indexed` is faster.
+    ///
     /// # Panics
     /// Panics if either of nodes does not exist in the graph.
     ///
     pub fn find_shortest_path<'a, Dist, Node: Eq + Hash + Debug + Copy + PartialEq, G: PathFindable<'a, Node, Dist>>(graph: &'a G, source: Node, target: Node) -> Option<Vec<Node>> 
         where Dist: num_traits::PrimInt {
 
-        // Initialize state
-        let mut distance: HashMap<Node, Dist> = HashMap::new();
-        let mut visited: HashMap<Node, bool> = HashMap::new();
-        let mut previous: HashMap<Node, Option<Node>> = HashMap::new();
-        let mut queue:std::collections::BinaryHeap<priority_node::PriorityNode<Dist, Node>> = std::collections::BinaryHeap::new();
+        let mut state = HashSearchState { distance: HashMap::new(), previous: HashMap::new(), visited: HashSet::new() };
+        PathFinder::dijkstra(|node| graph.get_neighbours(node), source, target, Dist::zero(), |dist, weight| dist + weight, &mut state)
+            .map(|(path, _)| path)
+    }
+
+    ///
+    /// Finds shortest path between a and b, using Dijkstra algorightm on vector-indexed state.
+    /// Returns None if there is no path between a and b.
+    ///
+    /// The state vectors start zeroed and only nodes reached by the search are written,
+    /// so a short query does not pay for the size of the graph.
+    ///
+    /// # Panics
+    /// Panics if either of nodes does not exist in the graph.
+    ///
+    pub fn find_shortest_path_indexed<'a, Dist, Node: Eq + Hash + Copy, G>(graph: &'a G, source: Node, target: Node) -> Option<Vec<Node>>
+        where G: PathFindable<'a, Node, Dist> + NodeIndexable<Node>, Dist: num_traits::PrimInt {
+
+        let mut state = DenseSearchState::new(graph);
+        PathFinder::dijkstra(|node| graph.get_neighbours(node), source, target, Dist::zero(), |dist, weight| dist + weight, &mut state)
+            .map(|(path, _)| path)
+    }
+
+    ///
+    /// Finds the path from a to b that arrives the earliest when leaving a at the departure time.
+    /// Every edge weight is a function of the time the edge is entered returning the time it is left.
+    /// Returns the path with its arrival time, or None if there is no path between a and b.
+    ///
+    /// The result is exact as long as waiting never pays off: every edge function has to return
+    /// a time not earlier than its argument and must not decrease when the argument grows (FIFO).
+    ///
+    pub fn find_earliest_arrival<'a, Time: Ord + Copy, F: Fn(Time) -> Time, Node: Eq + Hash + Copy, G>(graph: &'a G, source: Node, target: Node, departure: Time) -> Option<(Vec<Node>, Time)>
+        where G: PathFindable<'a, Node, F> {
+
+        let mut state = HashSearchState { distance: HashMap::new(), previous: HashMap::new(), visited: HashSet::new() };
+        PathFinder::dijkstra(|node| graph.get_neighbours(node), source, target, departure, |time, travel| travel(time), &mut state)
+    }
+
+    ///
+    /// Profile of earliest arrivals from a to b for departures in the from..=to window,
+    /// sampled every step. Only departures worth taking are returned: those that arrive strictly
+    /// earlier than any later departure does. Entries are (departure, arrival, path) in order of departure.
+    ///
+    /// Edge functions have the same requirements as in `find_earliest_arrival`.
+    ///
+    /// # Panics
+    /// Panics if step is zero.
+    ///
+    pub fn find_arrival_profile<'a, Time: num_traits::PrimInt, F: Fn(Time) -> Time, Node: Eq + Hash + Copy, G>(graph: &'a G, source: Node, target: Node, from: Time, to: Time, step: Time) -> Vec<(Time, Time, Vec<Node>)>
+        where G: PathFindable<'a, Node, F> {
+
+        assert!(step > Time::zero(), "Step has to be positive");
 
-        queue.push(priority_node::PriorityNode{priority: Dist::zero(), node: source});
-        let mut target_reached = false;
-        for node in graph.nodes() {
-            distance.insert(node, Dist::max_value());
-            previous.insert(node, None);
+        let mut departures = Vec::new();
+        let mut departure = from;
+        while departure <= to {
+            departures.push(departure);
+            departure = match departure.checked_add(&step) {
+                Some(next) => next,
+                None => break
+            };
         }
 
-        'outer: while !queue.is_empty() {
+        // Going from the latest departure, an earlier one is only useful if it arrives sooner
+        let mut profile: Vec<(Time, Time, Vec<Node>)> = Vec::new();
+        for departure in departures.into_iter().rev() {
+            if let Some((path, arrival)) = PathFinder::find_earliest_arrival(graph, source, target, departure) {
+                if profile.last().is_none_or(|(_, later_arrival, _)| arrival < *later_arrival) {
+                    profile.push((departure, arrival, path));
+                }
+            }
+        }
+
+        profile.reverse();
+        profile
+    }
+
+    // Dijkstra over any label that only grows along edges: `neighbours` lists the nodes next to a node
+    // with the weight of the edge leading to them, `relax` computes the label of a neighbour from
+    // the label of the current node and that weight
+    pub(crate) fn dijkstra<Dist: Ord + Copy, Weight, Node: Eq + Hash + Copy, I: Iterator<Item=(Node, Weight)>, S: SearchState<Node, Dist>>(neighbours: impl Fn(Node) -> I, source: Node, target: Node, start: Dist, relax: impl Fn(Dist, Weight) -> Dist, state: &mut S) -> Option<(Vec<Node>, Dist)> {
+
+        let mut queue: BinaryHeap<priority_node::PriorityNode<Dist, Node>> = BinaryHeap::new();
 
-            // Take vertex from queue with lowest distance value
-            let curr_vertex = queue.pop().unwrap(); // TODO: match on this instead of while?
+        // The target is tracked separately, so that for a == b the start of the search does not count as a path
+        let mut target_distance: Option<Dist> = None;
+        let mut target_previous: Option<Node> = None;
+
+        state.update(source, start, None);
+        queue.push(priority_node::PriorityNode{priority: start, node: source});
+
+        // Take vertex from queue with lowest distance value
+        while let Some(curr_vertex) = queue.pop() {
+
+            // Nothing left in the queue can be closer than the target already is
+            if target_distance.is_some_and(|dist| curr_vertex.priority >= dist) {
+                break;
+            }
 
             // Check if the vertex was not already handled. Since we are adding new vertices instead of decreasing
             // their priority, duplicates happen.
-            if visited.contains_key(&curr_vertex.node) {
+            if !state.visit(curr_vertex.node) {
                 continue;
             }
-            visited.insert(curr_vertex.node, true);
 
             // For every adjecent node
-            for neighbour in graph.get_neighbours(curr_vertex.node) {
+            for neighbour in neighbours(curr_vertex.node) {
                 // Check if distance to current node + distance to that neighbour is lower than its saved distance from source
-                let dist_through_curr_vertex = curr_vertex.priority + neighbour.1;
-                if  dist_through_curr_vertex < distance[&neighbour.0] {
-
-                    // If yes then replace that distance and add to queue
-                    distance.insert(neighbour.0, dist_through_curr_vertex);
-                    previous.insert(neighbour.0, Some(curr_vertex.node));
+                let dist_through_curr_vertex = relax(curr_vertex.priority, neighbour.1);
 
-                    // Finding the target in queue finishes the algorithm
-                    if neighbour.0 == target {
-                        target_reached = true;
-                        break 'outer;
+                if neighbour.0 == target {
+                    if target_distance.is_none_or(|dist| dist_through_curr_vertex < dist) {
+                        target_distance = Some(dist_through_curr_vertex);
+                        target_previous = Some(curr_vertex.node);
                     }
+                    continue;
+                }
+
+                if state.distance(neighbour.0).is_none_or(|dist| dist_through_curr_vertex < dist) {
 
+                    // If yes then replace that distance and add to queue
+                    state.update(neighbour.0, dist_through_curr_vertex, Some(curr_vertex.node));
                     queue.push(priority_node::PriorityNode{ priority: dist_through_curr_vertex, node: neighbour.0 });
                 }
             }
         }
 
         // If we haven't found the target, the path doesn't exist
-        if !target_reached {
-            return None
-        }
-
-        // Count the size of return vec
-        let mut curr_node = target;
-        let mut count = 0;
-        loop {
-            // If there is a previous, continue to it - there always should be
-            match previous[&curr_node] {
-                Some(i) => {
-                    curr_node = i;
-                    count += 1;
-                },
-                None => {
-                    assert_eq!(curr_node, source);
+        let target_distance = target_distance?;
+
+        let path = PathFinder::build_path(source, target, |node| {
+            if node == target { target_previous } else { state.previous(node) }
+        });
+        Some((path, target_distance))
+    }
+
+    ///
+    /// Finds shortest path between a and b counting every edge as 1, using BFS.
+    /// Edge weights are ignored. Returns None if there is no path between a and b.
+    ///
+    /// # Panics
+    /// Panics if either of nodes does not exist in the graph.
+    ///
+    pub fn find_shortest_path_unweighted<'a, Dist, Node: Copy + PartialEq, G>(graph: &'a G, source: Node, target: Node) -> Option<Vec<Node>>
+        where G: PathFindable<'a, Node, Dist> + NodeIndexable<Node> {
+
+        let mut visited = vec![false; graph.node_count()];
+        let mut previous: Vec<Option<Node>> = vec![None; graph.node_count()];
+        let mut queue = VecDeque::new();
+
+        visited[graph.index_of(source)] = true;
+        queue.push_back(source);
+
+        while let Some(curr_node) = queue.pop_front() {
+            for (neighbour, _) in graph.get_neighbours(curr_node) {
+                // Nodes are handled layer by layer, so the first edge leading to the target is on a shortest path.
+                // The target is checked before `visited`, so a path from a node to itself is a cycle.
+                if neighbour == target {
+                    previous[graph.index_of(target)] = Some(curr_node);
+                    return Some(PathFinder::build_path(source, target, |node| previous[graph.index_of(node)]));
+                }
+
+                let index = graph.index_of(neighbour);
+                if !visited[index] {
+                    visited[index] = true;
+                    previous[index] = Some(curr_node);
+                    queue.push_back(neighbour);
                 }
             }
-            if curr_node == source {
+        }
+
+        None
+    }
+
+    ///
+    /// Finds shortest path between a and b in a graph where every weight is either 0 or 1,
+    /// using 0-1 BFS. Returns None if there is no path between a and b.
+    ///
+    /// # Panics
+    /// Panics if either of nodes does not exist in the graph or if any visited edge
+    /// has a weight other than 0 or 1.
+    ///
+    pub fn find_shortest_path_zero_one<'a, Dist, Node: Copy + PartialEq, G>(graph: &'a G, source: Node, target: Node) -> Option<Vec<Node>>
+        where G: PathFindable<'a, Node, Dist> + NodeIndexable<Node>, Dist: num_traits::PrimInt {
+
+        let mut distance = vec![Dist::max_value(); graph.node_count()];
+        let mut visited = vec![false; graph.node_count()];
+        let mut previous: Vec<Option<Node>> = vec![None; graph.node_count()];
+        let mut queue = VecDeque::new();
+
+        // The target is tracked separately, so that for a == b the start of the search does not count as a path
+        let mut target_distance: Option<Dist> = None;
+
+        distance[graph.index_of(source)] = Dist::zero();
+        queue.push_back((source, Dist::zero()));
+
+        while let Some((curr_node, curr_distance)) = queue.pop_front() {
+            let curr_index = graph.index_of(curr_node);
+            if visited[curr_index] {
+                continue;
+            }
+            visited[curr_index] = true;
+
+            // The deque is sorted by distance, nothing left in it can improve the target
+            if target_distance.is_some_and(|dist| curr_distance >= dist) {
                 break;
             }
+
+            for (neighbour, weight) in graph.get_neighbours(curr_node) {
+                assert!(weight == Dist::zero() || weight == Dist::one(), "Edge weight has to be 0 or 1");
+                let dist_through_curr_node = curr_distance + weight;
+
+                if neighbour == target {
+                    if target_distance.is_none_or(|dist| dist_through_curr_node < dist) {
+                        target_distance = Some(dist_through_curr_node);
+                        previous[graph.index_of(target)] = Some(curr_node);
+                    }
+                    continue;
+                }
+
+                let index = graph.index_of(neighbour);
+                if dist_through_curr_node < distance[index] {
+                    distance[index] = dist_through_curr_node;
+                    previous[index] = Some(curr_node);
+
+                    // Zero edges keep the distance, so they go to the front
+                    if weight == Dist::zero() {
+                        queue.push_front((neighbour, dist_through_curr_node));
+                    } else {
+                        queue.push_back((neighbour, dist_through_curr_node));
+                    }
+                }
+            }
+        }
+
+        target_distance?;
+        Some(PathFinder::build_path(source, target, |node| previous[graph.index_of(node)]))
+    }
+
+    // Walks back from the target using the previous node of every node on the path.
+    // The target's previous node has to be set, even if it is the source.
+    fn build_path<Node: Copy + PartialEq>(source: Node, target: Node, previous: impl Fn(Node) -> Option<Node>) -> Vec<Node> {
+        let mut path = vec![target];
+        let mut curr_node = previous(target).expect("Target has no previous node");
+        while curr_node != source {
+            path.push(curr_node);
+            curr_node = previous(curr_node).expect("Path does not lead back to the source");
         }
+        path.push(source);
+        path.reverse();
+        path
+    }
 
-        // Allocate a zero vector and iterate over it from behind
-        let mut ret = vec![source; count + 1];
+    ///
+    /// Finds the path between a and b with the lowest first cost among the paths that respect
+    /// the constraints. Every edge weight is an array of costs, all of them have to be non-negative.
+    /// Returns the path with its summed costs, or None if no feasible path exists.
+    ///
+    /// Uses label setting: every node keeps the labels (costs and hop count of a partial path)
+    /// that are not dominated by another label, so the search is exact but can grow exponential
+    /// with many cost dimensions.
+    ///
+    pub fn find_constrained_path<'a, Dist, Node: Eq + Hash + Copy, G, const N: usize>(graph: &'a G, source: Node, target: Node, constraints: &PathConstraints<Node, Dist, N>) -> Option<(Vec<Node>, [Dist; N])>
+        where G: PathFindable<'a, Node, [Dist; N]>, Dist: num_traits::PrimInt {
 
-        // let mut ret = vec![0; distance[&target] + 1];
-        curr_node = target;
+        if constraints.forbidden_nodes.contains(&source) || constraints.forbidden_nodes.contains(&target) {
+            return None;
+        }
+
+        // Hop counts only matter for dominance if they are limited
+        let dominates = |a: &Label<Node, [Dist; N]>, b: &Label<Node, [Dist; N]>| {
+            a.cost.iter().zip(b.cost.iter()).all(|(a_cost, b_cost)| a_cost <= b_cost)
+                && (constraints.max_hops.is_none() || a.hops <= b.hops)
+        };
 
-        for elem in ret.iter_mut().rev() {
-            *elem = curr_node;
+        let mut labels = vec![Label { node: source, cost: [Dist::zero(); N], hops: 0, previous: None, dominated: false }];
+        let mut labels_at_node: HashMap<Node, Vec<usize>> = HashMap::from([(source, vec![0])]);
+        let mut queue = BinaryHeap::new();
+        queue.push(priority_node::PriorityNode{ priority: ([Dist::zero(); N], 0), node: 0 });
 
-            // If there is a previous, continue to it - there always should be
-            match previous[&curr_node] {
-                Some(i) => {
-                    curr_node = i;
-                },
-                None => {
-                    assert_eq!(curr_node, source);
+        // Labels come out ordered by their first cost, so the first one reaching the target is optimal
+        while let Some(entry) = queue.pop() {
+            let curr_label = &labels[entry.node];
+            if curr_label.dominated {
+                continue;
+            }
+
+            if curr_label.node == target && curr_label.previous.is_some() {
+                return Some((Label::build_path(&labels, entry.node), curr_label.cost));
+            }
+
+            if constraints.max_hops.is_some_and(|max_hops| curr_label.hops >= max_hops) {
+                continue;
+            }
+
+            let (curr_node, curr_cost, curr_hops) = (curr_label.node, curr_label.cost, curr_label.hops);
+            for (neighbour, weight) in graph.get_neighbours(curr_node) {
+                if constraints.forbidden_nodes.contains(&neighbour) || constraints.forbidden_edges.contains(&(curr_node, neighbour)) {
+                    continue;
+                }
+
+                let mut cost = curr_cost;
+                for (index, value) in cost.iter_mut().enumerate() {
+                    *value = *value + weight[index];
+                }
+                if cost.iter().zip(constraints.max_costs.iter()).any(|(cost, max_cost)| max_cost.is_some_and(|max_cost| *cost > max_cost)) {
+                    continue;
+                }
+
+                let label = Label { node: neighbour, cost, hops: curr_hops + 1, previous: Some(entry.node), dominated: false };
+
+                // Arrivals at the target are never extended, so they don't compete with the starting label when a == b
+                if neighbour != target && !Label::insert_non_dominated(&mut labels, labels_at_node.entry(neighbour).or_default(), &label, dominates) {
+                    continue;
                 }
+
+                queue.push(priority_node::PriorityNode{ priority: (label.cost, label.hops), node: labels.len() });
+                labels.push(label);
             }
         }
 
-        Some(ret)
+        None
+    }
+
+    ///
+    /// Finds the Pareto front of paths between a and b: every path whose cost is not dominated
+    /// by the cost of another path. Of paths with equal costs only one is returned.
+    /// Costs have to be non-negative in every criterion.
+    /// Paths are returned with their costs, in ascending order of costs.
+    ///
+    /// Uses multi-objective label setting (Martins' algorithm).
+    ///
+    pub fn find_pareto_paths<'a, Cost: ParetoCost, Node: Eq + Hash + Copy, G>(graph: &'a G, source: Node, target: Node) -> Vec<(Vec<Node>, Cost)>
+        where G: PathFindable<'a, Node, Cost> {
+
+        let dominates = |a: &Label<Node, Cost>, b: &Label<Node, Cost>| a.cost.dominates(&b.cost);
+
+        let mut labels = vec![Label { node: source, cost: Cost::zero(), hops: 0, previous: None, dominated: false }];
+        let mut labels_at_node: HashMap<Node, Vec<usize>> = HashMap::from([(source, vec![0])]);
+        // Kept apart from the labels of the source, so that for a == b the start of the search does not count as a path
+        let mut labels_at_target: Vec<usize> = Vec::new();
+        let mut front: Vec<(Vec<Node>, Cost)> = Vec::new();
+
+        let mut queue = BinaryHeap::new();
+        queue.push(priority_node::PriorityNode{ priority: Cost::zero(), node: 0 });
+
+        while let Some(entry) = queue.pop() {
+            let curr_label = &labels[entry.node];
+            if curr_label.dominated {
+                continue;
+            }
+
+            // Nothing popped later can dominate a label, so arrivals at the target are final
+            if curr_label.node == target && curr_label.previous.is_some() {
+                front.push((Label::build_path(&labels, entry.node), curr_label.cost));
+                continue;
+            }
+
+            let (curr_node, curr_cost, curr_hops) = (curr_label.node, curr_label.cost, curr_label.hops);
+            for (neighbour, weight) in graph.get_neighbours(curr_node) {
+                let label = Label { node: neighbour, cost: curr_cost.add(&weight), hops: curr_hops + 1, previous: Some(entry.node), dominated: false };
+
+                // Nothing extending a label dominated by the front can join it
+                if front.iter().any(|(_, cost)| cost.dominates(&label.cost)) {
+                    continue;
+                }
+
+                let node_labels = if neighbour == target {
+                    &mut labels_at_target
+                } else {
+                    labels_at_node.entry(neighbour).or_default()
+                };
+                if !Label::insert_non_dominated(&mut labels, node_labels, &label, dominates) {
+                    continue;
+                }
 
+                queue.push(priority_node::PriorityNode{ priority: label.cost, node: labels.len() });
+                labels.push(label);
+            }
+        }
+
+        front
     }
 
     ///
@@ -165,4 +544,137 @@ impl PathFinder {
             }
         }
     }
-}
\ No newline at end of file
+}
+
+// Per node bookkeeping of the Dijkstra search
+pub(crate) trait SearchState<Node, Dist> {
+    fn distance(&self, n: Node) -> Option<Dist>;
+    fn previous(&self, n: Node) -> Option<Node>;
+    fn update(&mut self, n: Node, distance: Dist, previous: Option<Node>);
+
+    // Marks the node as visited, returns false if it already was
+    fn visit(&mut self, n: Node) -> bool;
+}
+
+struct HashSearchState<Node, Dist> {
+    distance: HashMap<Node, Dist>,
+    previous: HashMap<Node, Node>,
+    visited: HashSet<Node>
+}
+
+impl<Node: Eq + Hash + Copy, Dist: Copy> SearchState<Node, Dist> for HashSearchState<Node, Dist> {
+    fn distance(&self, n: Node) -> Option<Dist> {
+        self.distance.get(&n).copied()
+    }
+
+    fn previous(&self, n: Node) -> Option<Node> {
+        self.previous.get(&n).copied()
+    }
+
+    fn update(&mut self, n: Node, distance: Dist, previous: Option<Node>) {
+        self.distance.insert(n, distance);
+        if let Some(previous) = previous {
+            self.previous.insert(n, previous);
+        }
+    }
+
+    fn visit(&mut self, n: Node) -> bool {
+        self.visited.insert(n)
+    }
+}
+
+struct DenseEntry<Node, Dist> {
+    distance: Dist,
+    previous: Option<Node>,
+    visited: bool
+}
+
+// Nodes reached by the search get an entry, `slots` maps node index to entry index + 1
+// and 0 means the node wasn't reached yet
+pub(crate) struct DenseSearchState<'g, Node, Dist, G> {
+    graph: &'g G,
+    slots: Vec<usize>,
+    entries: Vec<DenseEntry<Node, Dist>>
+}
+
+impl<'g, Node, Dist, G: NodeIndexable<Node>> DenseSearchState<'g, Node, Dist, G> {
+    pub(crate) fn new(graph: &'g G) -> Self {
+        DenseSearchState { graph, slots: vec![0; graph.node_count()], entries: Vec::new() }
+    }
+
+    fn entry(&self, n: Node) -> Option<&DenseEntry<Node, Dist>> {
+        match self.slots[self.graph.index_of(n)] {
+            0 => None,
+            slot => Some(&self.entries[slot - 1])
+        }
+    }
+}
+
+impl<Node: Copy, Dist: Copy, G: NodeIndexable<Node>> SearchState<Node, Dist> for DenseSearchState<'_, Node, Dist, G> {
+    fn distance(&self, n: Node) -> Option<Dist> {
+        self.entry(n).map(|entry| entry.distance)
+    }
+
+    fn previous(&self, n: Node) -> Option<Node> {
+        self.entry(n).and_then(|entry| entry.previous)
+    }
+
+    fn update(&mut self, n: Node, distance: Dist, previous: Option<Node>) {
+        let index = self.graph.index_of(n);
+        match self.slots[index] {
+            0 => {
+                self.entries.push(DenseEntry { distance, previous, visited: false });
+                self.slots[index] = self.entries.len();
+            },
+            slot => {
+                let entry = &mut self.entries[slot - 1];
+                entry.distance = distance;
+                entry.previous = previous;
+            }
+        }
+    }
+
+    fn visit(&mut self, n: Node) -> bool {
+        let slot = self.slots[self.graph.index_of(n)];
+        let entry = &mut self.entries[slot - 1];
+        !std::mem::replace(&mut entry.visited, true)
+    }
+}
+
+// Partial path of the label setting searches, `previous` is the index of the label it was extended from
+struct Label<Node, C> {
+    node: Node,
+    cost: C,
+    hops: usize,
+    previous: Option<usize>,
+    dominated: bool
+}
+
+impl<Node: Copy, C> Label<Node, C> {
+    // Adds the label to the labels kept at its node unless one of them dominates it.
+    // Labels it dominates are dropped from the node and marked, so the queue skips them.
+    // Returns false if the label was rejected.
+    fn insert_non_dominated(labels: &mut [Label<Node, C>], node_labels: &mut Vec<usize>, label: &Label<Node, C>, dominates: impl Fn(&Label<Node, C>, &Label<Node, C>) -> bool) -> bool {
+        if node_labels.iter().any(|index| dominates(&labels[*index], label)) {
+            return false;
+        }
+        node_labels.retain(|index| {
+            let is_dominated = dominates(label, &labels[*index]);
+            labels[*index].dominated |= is_dominated;
+            !is_dominated
+        });
+        node_labels.push(labels.len());
+        true
+    }
+
+    fn build_path(labels: &[Label<Node, C>], last: usize) -> Vec<Node> {
+        let mut path = Vec::new();
+        let mut curr_index = Some(last);
+        while let Some(index) = curr_index {
+            path.push(labels[index].node);
+            curr_index = labels[index].previous;
+        }
+        path.reverse();
+        path
+    }
+}
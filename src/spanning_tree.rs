@@ -0,0 +1,183 @@
+use std::collections::BinaryHeap;
+
+use crate::path_finder::{NodeIndexable, EdgeIndexable};
+use crate::priority_node::PriorityNode;
+use crate::union_find::UnionFind;
+use crate::weighted_graph::{Node, Edge, WeightedGraph};
+
+pub struct SpanningTree;
+
+// Minimum spanning forest: a minimum spanning tree of every connected component.
+// Each edge is one of the two copies listed by its nodes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpanningForest<W> {
+    pub edges: Vec<Edge>,
+    pub total_weight: W
+}
+
+impl SpanningTree {
+
+    ///
+    /// Finds a minimum spanning forest of an undirected graph using Kruskal's algorithm.
+    /// O(E log E)
+    ///
+    /// # Panics
+    /// Panics if the graph is directed.
+    ///
+    pub fn kruskal<'a, W, G>(graph: &'a G) -> SpanningForest<W>
+        where G: EdgeIndexable<'a, Node, Edge, W> + NodeIndexable<Node>, W: Copy + Ord + num_traits::Zero {
+
+        let mut edges = SpanningTree::unique_edges(graph);
+        edges.sort_by_key(|(edge, _, _, weight)| (*weight, graph.edge_index(*edge)));
+
+        let mut sets = UnionFind::new(graph.node_count());
+        let mut forest = SpanningForest { edges: Vec::new(), total_weight: W::zero() };
+        for (edge, source, target, weight) in edges {
+            if sets.union(source, target) {
+                forest.edges.push(edge);
+                forest.total_weight = forest.total_weight + weight;
+            }
+        }
+        forest
+    }
+
+    ///
+    /// Finds a minimum spanning forest of an undirected graph using Prim's algorithm,
+    /// growing a tree from the lowest node index of every component.
+    /// O(E log E)
+    ///
+    /// # Panics
+    /// Panics if the graph is directed.
+    ///
+    pub fn prim<'a, W, G>(graph: &'a G) -> SpanningForest<W>
+        where G: EdgeIndexable<'a, Node, Edge, W> + NodeIndexable<Node>, W: Copy + Ord + num_traits::Zero {
+
+        assert!(!graph.is_directed(), "Spanning trees are only defined for undirected graphs");
+
+        let mut in_tree = vec![false; graph.node_count()];
+        let mut forest = SpanningForest { edges: Vec::new(), total_weight: W::zero() };
+
+        // The queue holds positions in `candidates`, edges leading out of the tree
+        let mut candidates: Vec<(Edge, usize)> = Vec::new();
+        let mut queue = BinaryHeap::new();
+
+        for root in 0..graph.node_count() {
+            if in_tree[root] {
+                continue;
+            }
+
+            SpanningTree::add_to_tree(graph, root, &mut in_tree, &mut candidates, &mut queue);
+            while let Some(PriorityNode { priority: weight, node: candidate }) = queue.pop() {
+                let (edge, target) = candidates[candidate];
+                if in_tree[target] {
+                    continue;
+                }
+                forest.edges.push(edge);
+                forest.total_weight = forest.total_weight + weight;
+                SpanningTree::add_to_tree(graph, target, &mut in_tree, &mut candidates, &mut queue);
+            }
+        }
+        forest
+    }
+
+    ///
+    /// Finds a minimum spanning forest of an undirected graph using Borůvka's algorithm:
+    /// in every round each component joins the cheapest edge leaving it.
+    /// Ties are broken by edge index, so the forest is the same as Kruskal's.
+    /// O(E log V)
+    ///
+    /// # Panics
+    /// Panics if the graph is directed.
+    ///
+    pub fn boruvka<'a, W, G>(graph: &'a G) -> SpanningForest<W>
+        where G: EdgeIndexable<'a, Node, Edge, W> + NodeIndexable<Node>, W: Copy + Ord + num_traits::Zero {
+
+        let edges = SpanningTree::unique_edges(graph);
+        let mut sets = UnionFind::new(graph.node_count());
+        let mut forest = SpanningForest { edges: Vec::new(), total_weight: W::zero() };
+
+        loop {
+            // Cheapest edge leaving every component, by position in `edges`
+            let mut cheapest: Vec<Option<usize>> = vec![None; graph.node_count()];
+            for (position, (edge, source, target, weight)) in edges.iter().enumerate() {
+                let (source_set, target_set) = (sets.find(*source), sets.find(*target));
+                if source_set == target_set {
+                    continue;
+                }
+                for set in [source_set, target_set] {
+                    let is_cheaper = cheapest[set].is_none_or(|current| {
+                        let (current_edge, _, _, current_weight) = edges[current];
+                        (*weight, graph.edge_index(*edge)) < (current_weight, graph.edge_index(current_edge))
+                    });
+                    if is_cheaper {
+                        cheapest[set] = Some(position);
+                    }
+                }
+            }
+
+            let mut joined = false;
+            for position in cheapest.into_iter().flatten() {
+                let (edge, source, target, weight) = edges[position];
+                if sets.union(source, target) {
+                    forest.edges.push(edge);
+                    forest.total_weight = forest.total_weight + weight;
+                    joined = true;
+                }
+            }
+
+            if !joined {
+                return forest;
+            }
+        }
+    }
+
+    ///
+    /// Builds an undirected graph with every node of the graph, under the same indices,
+    /// and only the edges of the forest.
+    /// O(V + E)
+    ///
+    pub fn build_graph<T: Clone, W: Clone>(graph: &WeightedGraph<T, W>, forest: &SpanningForest<W>) -> WeightedGraph<T, W> {
+        let mut tree = WeightedGraph::new_undirected();
+        for (_, value) in graph.nodes() {
+            tree.add_node(value.clone());
+        }
+        for edge in forest.edges.iter() {
+            tree.add_edge(edge.source, edge.target, graph.get_weight(*edge).clone());
+        }
+        tree
+    }
+
+    // Part of Prim's algorithm, queues edges from the node leading out of the tree
+    fn add_to_tree<'a, W, G>(graph: &'a G, index: usize, in_tree: &mut [bool], candidates: &mut Vec<(Edge, usize)>, queue: &mut BinaryHeap<PriorityNode<W, usize>>)
+        where G: EdgeIndexable<'a, Node, Edge, W> + NodeIndexable<Node>, W: Ord {
+
+        in_tree[index] = true;
+        for (edge, neighbour, weight) in graph.get_edges(graph.node_at(index)) {
+            let neighbour = graph.index_of(neighbour);
+            if !in_tree[neighbour] {
+                queue.push(PriorityNode { priority: weight, node: candidates.len() });
+                candidates.push((edge, neighbour));
+            }
+        }
+    }
+
+    // Every undirected edge once, with node indices of its ends
+    fn unique_edges<'a, W, G>(graph: &'a G) -> Vec<(Edge, usize, usize, W)>
+        where G: EdgeIndexable<'a, Node, Edge, W> + NodeIndexable<Node> {
+
+        assert!(!graph.is_directed(), "Spanning trees are only defined for undirected graphs");
+
+        let mut listed = vec![false; graph.edge_count()];
+        let mut edges = Vec::with_capacity(graph.edge_count());
+        for index in 0..graph.node_count() {
+            for (edge, neighbour, weight) in graph.get_edges(graph.node_at(index)) {
+                let edge_index = graph.edge_index(edge);
+                if !listed[edge_index] {
+                    listed[edge_index] = true;
+                    edges.push((edge, index, graph.index_of(neighbour), weight));
+                }
+            }
+        }
+        edges
+    }
+}
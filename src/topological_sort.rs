@@ -0,0 +1,144 @@
+use std::{cmp::Reverse, collections::BinaryHeap, fmt};
+
+use crate::path_finder::{PathFindable, NodeIndexable};
+use crate::traversal::{Dfs, TraversalEvent};
+
+pub struct TopologicalSort;
+
+// Returned when the graph has no topological order. Every node of the cycle has an edge
+// to the next one, and the last node has an edge to the first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CycleError<Node> {
+    pub cycle: Vec<Node>
+}
+
+impl<Node: fmt::Debug> fmt::Display for CycleError<Node> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "graph contains a cycle: {:?}", self.cycle)
+    }
+}
+
+impl<Node: fmt::Debug> std::error::Error for CycleError<Node> {}
+
+impl TopologicalSort {
+
+    ///
+    /// Orders nodes so that every edge leads from an earlier node to a later one,
+    /// using reversed DFS postorder. Fails with one of the cycles if the graph has any.
+    ///
+    pub fn sort<'a, Node: Copy + PartialEq, Dist, G>(graph: &'a G) -> Result<Vec<Node>, CycleError<Node>>
+        where G: PathFindable<'a, Node, Dist> + NodeIndexable<Node> {
+
+        let mut parent: Vec<Option<Node>> = vec![None; graph.node_count()];
+        let mut order = Vec::with_capacity(graph.node_count());
+
+        for event in Dfs::new(graph, graph.nodes()) {
+            match event {
+                TraversalEvent::TreeEdge(source, target) => parent[graph.index_of(target)] = Some(source),
+                TraversalEvent::Finish { node, .. } => order.push(node),
+
+                // The target is still on the DFS stack, so tree edges lead from it back to the source
+                TraversalEvent::BackEdge(source, target) => {
+                    let mut cycle = vec![source];
+                    let mut curr_node = source;
+                    while curr_node != target {
+                        curr_node = parent[graph.index_of(curr_node)].expect("Back edge target is not an ancestor");
+                        cycle.push(curr_node);
+                    }
+                    cycle.reverse();
+                    return Err(CycleError { cycle });
+                },
+                _ => ()
+            }
+        }
+
+        order.reverse();
+        Ok(order)
+    }
+
+    ///
+    /// Splits nodes into generations using Kahn's algorithm: the first generation has no incoming
+    /// edges and every later one only has incoming edges from earlier generations, so nodes of
+    /// one generation can be processed in parallel. Fails with one of the cycles if the graph has any.
+    ///
+    pub fn generations<'a, Node: Copy + PartialEq, Dist, G>(graph: &'a G) -> Result<Vec<Vec<Node>>, CycleError<Node>>
+        where G: PathFindable<'a, Node, Dist> + NodeIndexable<Node> {
+
+        let mut in_degree = TopologicalSort::in_degrees(graph);
+        let mut generations: Vec<Vec<Node>> = Vec::new();
+        let mut sorted_count = 0;
+
+        let mut generation: Vec<Node> = graph.nodes().filter(|node| in_degree[graph.index_of(*node)] == 0).collect();
+        while !generation.is_empty() {
+            let mut next_generation = Vec::new();
+            for node in generation.iter() {
+                for (neighbour, _) in graph.get_neighbours(*node) {
+                    let index = graph.index_of(neighbour);
+                    in_degree[index] -= 1;
+                    if in_degree[index] == 0 {
+                        next_generation.push(neighbour);
+                    }
+                }
+            }
+            sorted_count += generation.len();
+            generations.push(generation);
+            generation = next_generation;
+        }
+
+        if sorted_count < graph.node_count() {
+            return Err(TopologicalSort::sort(graph).err().expect("Kahn's algorithm left nodes of an acyclic graph"));
+        }
+        Ok(generations)
+    }
+
+    ///
+    /// Finds the topological order that is lexicographically smallest by the key of each node,
+    /// using Kahn's algorithm with a priority queue. Ties are broken by node index.
+    /// Fails with one of the cycles if the graph has any.
+    ///
+    /// Pass `|node| graph[node]` to order by node values.
+    ///
+    pub fn sort_by_key<'a, Node: Copy + PartialEq, Dist, G, K: Ord>(graph: &'a G, mut key: impl FnMut(Node) -> K) -> Result<Vec<Node>, CycleError<Node>>
+        where G: PathFindable<'a, Node, Dist> + NodeIndexable<Node> {
+
+        let mut in_degree = TopologicalSort::in_degrees(graph);
+        let mut order = Vec::with_capacity(graph.node_count());
+        let mut queue = BinaryHeap::new();
+
+        for node in graph.nodes() {
+            let index = graph.index_of(node);
+            if in_degree[index] == 0 {
+                queue.push(Reverse((key(node), index)));
+            }
+        }
+
+        while let Some(Reverse((_, index))) = queue.pop() {
+            let node = graph.node_at(index);
+            order.push(node);
+            for (neighbour, _) in graph.get_neighbours(node) {
+                let neighbour_index = graph.index_of(neighbour);
+                in_degree[neighbour_index] -= 1;
+                if in_degree[neighbour_index] == 0 {
+                    queue.push(Reverse((key(neighbour), neighbour_index)));
+                }
+            }
+        }
+
+        if order.len() < graph.node_count() {
+            return Err(TopologicalSort::sort(graph).err().expect("Kahn's algorithm left nodes of an acyclic graph"));
+        }
+        Ok(order)
+    }
+
+    fn in_degrees<'a, Node: Copy, Dist, G>(graph: &'a G) -> Vec<usize>
+        where G: PathFindable<'a, Node, Dist> + NodeIndexable<Node> {
+
+        let mut in_degree = vec![0; graph.node_count()];
+        for node in graph.nodes() {
+            for (neighbour, _) in graph.get_neighbours(node) {
+                in_degree[graph.index_of(neighbour)] += 1;
+            }
+        }
+        in_degree
+    }
+}
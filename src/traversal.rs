@@ -0,0 +1,306 @@
+use std::collections::VecDeque;
+
+use crate::path_finder::{PathFindable, NodeIndexable};
+
+// Events emitted while traversing a graph. Times come from one counter shared by discovering
+// and finishing nodes, depth is the number of tree edges from the root.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraversalEvent<Node> {
+    Discover { node: Node, time: usize, depth: usize },
+
+    // All edges leaving the node were examined
+    Finish { node: Node, time: usize },
+
+    // Edge that discovered its target
+    TreeEdge(Node, Node),
+
+    // DFS only: edge to a node that is still being explored. In undirected graphs the
+    // way back to the parent is one of them.
+    BackEdge(Node, Node),
+
+    // DFS only: edge to an already finished descendant
+    ForwardEdge(Node, Node),
+
+    // DFS only: edge to an already finished node that is not a descendant
+    CrossEdge(Node, Node),
+
+    // BFS only: edge to an already discovered node
+    NonTreeEdge(Node, Node)
+}
+
+// Callbacks for `Bfs::visit` and `Dfs::visit`, every one of them does nothing by default
+pub trait Visitor<Node> {
+    fn discover(&mut self, _node: Node, _time: usize, _depth: usize) {}
+    fn finish(&mut self, _node: Node, _time: usize) {}
+    fn tree_edge(&mut self, _source: Node, _target: Node) {}
+    fn back_edge(&mut self, _source: Node, _target: Node) {}
+    fn forward_edge(&mut self, _source: Node, _target: Node) {}
+    fn cross_edge(&mut self, _source: Node, _target: Node) {}
+    fn non_tree_edge(&mut self, _source: Node, _target: Node) {}
+}
+
+impl<Node> TraversalEvent<Node> {
+    fn dispatch<V: Visitor<Node>>(self, visitor: &mut V) {
+        match self {
+            TraversalEvent::Discover { node, time, depth } => visitor.discover(node, time, depth),
+            TraversalEvent::Finish { node, time } => visitor.finish(node, time),
+            TraversalEvent::TreeEdge(source, target) => visitor.tree_edge(source, target),
+            TraversalEvent::BackEdge(source, target) => visitor.back_edge(source, target),
+            TraversalEvent::ForwardEdge(source, target) => visitor.forward_edge(source, target),
+            TraversalEvent::CrossEdge(source, target) => visitor.cross_edge(source, target),
+            TraversalEvent::NonTreeEdge(source, target) => visitor.non_tree_edge(source, target)
+        }
+    }
+}
+
+// Neighbours of a node still waiting to be examined
+type Neighbours<'a, Node, Dist> = Box<dyn Iterator<Item=(Node, Dist)> + 'a>;
+
+// Per node times and depths, shared by both traversals
+struct Timestamps {
+    discovered: Vec<Option<usize>>,
+    finished: Vec<Option<usize>>,
+    depth: Vec<usize>,
+    time: usize
+}
+
+impl Timestamps {
+    fn new(node_count: usize) -> Self {
+        Timestamps { discovered: vec![None; node_count], finished: vec![None; node_count], depth: vec![0; node_count], time: 0 }
+    }
+
+    fn discover<Node>(&mut self, node: Node, index: usize, depth: usize) -> TraversalEvent<Node> {
+        self.discovered[index] = Some(self.time);
+        self.depth[index] = depth;
+        self.time += 1;
+        TraversalEvent::Discover { node, time: self.time - 1, depth }
+    }
+
+    fn finish<Node>(&mut self, node: Node, index: usize) -> TraversalEvent<Node> {
+        self.finished[index] = Some(self.time);
+        self.time += 1;
+        TraversalEvent::Finish { node, time: self.time - 1 }
+    }
+}
+
+///
+/// Depth first traversal yielding TraversalEvents. Roots are explored one after another,
+/// a root discovered from an earlier one is skipped.
+///
+/// # Panics
+/// Panics if any of the roots does not exist in the graph.
+///
+pub struct Dfs<'a, Node, Dist, G> {
+    graph: &'a G,
+    roots: std::vec::IntoIter<Node>,
+    stack: Vec<(Node, Neighbours<'a, Node, Dist>)>,
+    timestamps: Timestamps,
+    pending: Option<TraversalEvent<Node>>
+}
+
+impl<'a, Node: Copy, Dist, G: PathFindable<'a, Node, Dist> + NodeIndexable<Node>> Dfs<'a, Node, Dist, G> {
+    pub fn new<I: IntoIterator<Item = Node>>(graph: &'a G, roots: I) -> Self {
+        Dfs {
+            graph,
+            roots: roots.into_iter().collect::<Vec<Node>>().into_iter(),
+            stack: Vec::new(),
+            timestamps: Timestamps::new(graph.node_count()),
+            pending: None
+        }
+    }
+
+    // Nodes in order of discovery
+    pub fn preorder(self) -> impl Iterator<Item = Node> + 'a where Dist: 'a, G: 'a, Node: 'a {
+        self.filter_map(|event| match event {
+            TraversalEvent::Discover { node, .. } => Some(node),
+            _ => None
+        })
+    }
+
+    // Nodes in order of finishing
+    pub fn postorder(self) -> impl Iterator<Item = Node> + 'a where Dist: 'a, G: 'a, Node: 'a {
+        self.filter_map(|event| match event {
+            TraversalEvent::Finish { node, .. } => Some(node),
+            _ => None
+        })
+    }
+
+    // Runs the whole traversal, reporting every event to the visitor
+    pub fn visit<V: Visitor<Node>>(self, visitor: &mut V) {
+        for event in self {
+            event.dispatch(visitor);
+        }
+    }
+
+    pub fn discovery_time(&self, node: Node) -> Option<usize> {
+        self.timestamps.discovered[self.graph.index_of(node)]
+    }
+
+    pub fn finish_time(&self, node: Node) -> Option<usize> {
+        self.timestamps.finished[self.graph.index_of(node)]
+    }
+
+    // Number of tree edges between the node and its root, None if it wasn't discovered yet
+    pub fn depth(&self, node: Node) -> Option<usize> {
+        let index = self.graph.index_of(node);
+        self.timestamps.discovered[index].map(|_| self.timestamps.depth[index])
+    }
+
+    fn enter(&mut self, node: Node, depth: usize) -> TraversalEvent<Node> {
+        self.stack.push((node, self.graph.get_neighbours(node)));
+        self.timestamps.discover(node, self.graph.index_of(node), depth)
+    }
+}
+
+impl<'a, Node: Copy, Dist, G: PathFindable<'a, Node, Dist> + NodeIndexable<Node>> Iterator for Dfs<'a, Node, Dist, G> {
+    type Item = TraversalEvent<Node>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(event) = self.pending.take() {
+            return Some(event);
+        }
+
+        loop {
+            let Some((curr_node, neighbours)) = self.stack.last_mut() else {
+                // Start from the next root that wasn't reached yet
+                let root = self.roots.next()?;
+                if self.timestamps.discovered[self.graph.index_of(root)].is_none() {
+                    return Some(self.enter(root, 0));
+                }
+                continue;
+            };
+            let curr_node = *curr_node;
+
+            let Some((neighbour, _)) = neighbours.next() else {
+                self.stack.pop();
+                return Some(self.timestamps.finish(curr_node, self.graph.index_of(curr_node)));
+            };
+
+            let curr_index = self.graph.index_of(curr_node);
+            let index = self.graph.index_of(neighbour);
+            return Some(match (self.timestamps.discovered[index], self.timestamps.finished[index]) {
+                (None, _) => {
+                    self.pending = Some(self.enter(neighbour, self.timestamps.depth[curr_index] + 1));
+                    TraversalEvent::TreeEdge(curr_node, neighbour)
+                },
+                (Some(_), None) => TraversalEvent::BackEdge(curr_node, neighbour),
+                (Some(discovered), Some(_)) => {
+                    if self.timestamps.discovered[curr_index].is_some_and(|curr_discovered| curr_discovered < discovered) {
+                        TraversalEvent::ForwardEdge(curr_node, neighbour)
+                    } else {
+                        TraversalEvent::CrossEdge(curr_node, neighbour)
+                    }
+                }
+            });
+        }
+    }
+}
+
+///
+/// Breadth first traversal yielding TraversalEvents. All roots are discovered first at depth 0,
+/// so depths are distances from the closest root.
+///
+/// # Panics
+/// Panics if any of the roots does not exist in the graph.
+///
+pub struct Bfs<'a, Node, Dist, G> {
+    graph: &'a G,
+    roots: std::vec::IntoIter<Node>,
+    queue: VecDeque<Node>,
+    current: Option<(Node, Neighbours<'a, Node, Dist>)>,
+    timestamps: Timestamps,
+    pending: Option<TraversalEvent<Node>>
+}
+
+impl<'a, Node: Copy, Dist, G: PathFindable<'a, Node, Dist> + NodeIndexable<Node>> Bfs<'a, Node, Dist, G> {
+    pub fn new<I: IntoIterator<Item = Node>>(graph: &'a G, roots: I) -> Self {
+        Bfs {
+            graph,
+            roots: roots.into_iter().collect::<Vec<Node>>().into_iter(),
+            queue: VecDeque::new(),
+            current: None,
+            timestamps: Timestamps::new(graph.node_count()),
+            pending: None
+        }
+    }
+
+    // Nodes in order of discovery
+    pub fn preorder(self) -> impl Iterator<Item = Node> + 'a where Dist: 'a, G: 'a, Node: 'a {
+        self.filter_map(|event| match event {
+            TraversalEvent::Discover { node, .. } => Some(node),
+            _ => None
+        })
+    }
+
+    // Nodes in order of finishing
+    pub fn postorder(self) -> impl Iterator<Item = Node> + 'a where Dist: 'a, G: 'a, Node: 'a {
+        self.filter_map(|event| match event {
+            TraversalEvent::Finish { node, .. } => Some(node),
+            _ => None
+        })
+    }
+
+    // Runs the whole traversal, reporting every event to the visitor
+    pub fn visit<V: Visitor<Node>>(self, visitor: &mut V) {
+        for event in self {
+            event.dispatch(visitor);
+        }
+    }
+
+    pub fn discovery_time(&self, node: Node) -> Option<usize> {
+        self.timestamps.discovered[self.graph.index_of(node)]
+    }
+
+    pub fn finish_time(&self, node: Node) -> Option<usize> {
+        self.timestamps.finished[self.graph.index_of(node)]
+    }
+
+    // Number of tree edges between the node and its root, None if it wasn't discovered yet
+    pub fn depth(&self, node: Node) -> Option<usize> {
+        let index = self.graph.index_of(node);
+        self.timestamps.discovered[index].map(|_| self.timestamps.depth[index])
+    }
+}
+
+impl<'a, Node: Copy, Dist, G: PathFindable<'a, Node, Dist> + NodeIndexable<Node>> Iterator for Bfs<'a, Node, Dist, G> {
+    type Item = TraversalEvent<Node>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(event) = self.pending.take() {
+            return Some(event);
+        }
+
+        // Discover all roots before anything else
+        for root in self.roots.by_ref() {
+            let index = self.graph.index_of(root);
+            if self.timestamps.discovered[index].is_none() {
+                self.queue.push_back(root);
+                return Some(self.timestamps.discover(root, index, 0));
+            }
+        }
+
+        loop {
+            let Some((curr_node, neighbours)) = self.current.as_mut() else {
+                let next_node = self.queue.pop_front()?;
+                self.current = Some((next_node, self.graph.get_neighbours(next_node)));
+                continue;
+            };
+            let curr_node = *curr_node;
+
+            let Some((neighbour, _)) = neighbours.next() else {
+                self.current = None;
+                return Some(self.timestamps.finish(curr_node, self.graph.index_of(curr_node)));
+            };
+
+            let index = self.graph.index_of(neighbour);
+            if self.timestamps.discovered[index].is_some() {
+                return Some(TraversalEvent::NonTreeEdge(curr_node, neighbour));
+            }
+
+            let depth = self.timestamps.depth[self.graph.index_of(curr_node)] + 1;
+            self.queue.push_back(neighbour);
+            self.pending = Some(self.timestamps.discover(neighbour, index, depth));
+            return Some(TraversalEvent::TreeEdge(curr_node, neighbour));
+        }
+    }
+}
@@ -53,6 +53,10 @@ impl GraphType for Undirected {
         self.adjacency_list.len()
     }
 
+    fn is_directed(&self) -> bool {
+        false
+    }
+
     fn new() -> Self {
         Undirected { 
             adjacency_list: adjacency_list::AdjancencyList::new()
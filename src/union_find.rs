@@ -0,0 +1,103 @@
+///
+/// Disjoint sets over elements 0..len, with union by size and path halving.
+///
+pub struct UnionFind {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    set_count: usize
+}
+
+impl UnionFind {
+
+    ///
+    /// Creates len elements, every one of them in a set of its own.
+    /// O(n)
+    ///
+    pub fn new(len: usize) -> Self {
+        UnionFind { parent: (0..len).collect(), size: vec![1; len], set_count: len }
+    }
+
+    ///
+    /// Number of elements.
+    /// O(1)
+    ///
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    ///
+    /// Checks whether there are no elements.
+    /// O(1)
+    ///
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    ///
+    /// Number of disjoint sets.
+    /// O(1)
+    ///
+    pub fn set_count(&self) -> usize {
+        self.set_count
+    }
+
+    ///
+    /// Representative of the set holding the element, the same for every element of the set
+    /// until the set is merged with another one.
+    /// O(α(n)) amortized
+    ///
+    /// # Panics
+    /// Panics if the element is not lower than len.
+    ///
+    pub fn find(&mut self, mut element: usize) -> usize {
+        while self.parent[element] != element {
+            self.parent[element] = self.parent[self.parent[element]];
+            element = self.parent[element];
+        }
+        element
+    }
+
+    ///
+    /// Merges sets of both elements, returns false if they already were in one set.
+    /// O(α(n)) amortized
+    ///
+    /// # Panics
+    /// Panics if either of elements is not lower than len.
+    ///
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        if self.size[a] < self.size[b] {
+            std::mem::swap(&mut a, &mut b);
+        }
+        self.parent[b] = a;
+        self.size[a] += self.size[b];
+        self.set_count -= 1;
+        true
+    }
+
+    ///
+    /// Checks whether both elements are in the same set.
+    /// O(α(n)) amortized
+    ///
+    /// # Panics
+    /// Panics if either of elements is not lower than len.
+    ///
+    pub fn same_set(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    ///
+    /// Number of elements in the set holding the element.
+    /// O(α(n)) amortized
+    ///
+    /// # Panics
+    /// Panics if the element is not lower than len.
+    ///
+    pub fn set_size(&mut self, element: usize) -> usize {
+        let root = self.find(element);
+        self.size[root]
+    }
+}
@@ -0,0 +1,576 @@
+// Maximum weight matching with Edmonds' weighted blossom algorithm, following the primal-dual
+// method of Galil "Efficient algorithms for finding maximum matching in graphs" (1986).
+//
+// Edge k connects endpoints[2k] and endpoints[2k + 1], an endpoint p belongs to edge p / 2
+// and p ^ 1 is the other end of that edge. Nodes are blossoms 0..n, contracted blossoms
+// get the numbers n..2n.
+//
+// Dual variables of nodes are kept doubled, so that integer weights only need integer arithmetic.
+
+// Labels of top-level blossoms, BREADCRUMB marks blossoms seen by scan_blossom
+const FREE: u8 = 0;
+const EVEN: u8 = 1;
+const ODD: u8 = 2;
+const BREADCRUMB: u8 = 4;
+
+pub(crate) struct WeightedBlossom<W> {
+    node_count: usize,
+    edges: Vec<(usize, usize, W)>,
+    endpoints: Vec<usize>,
+
+    // Remote endpoints of edges of every node
+    neighbour_ends: Vec<Vec<usize>>,
+
+    // Remote endpoint of the matched edge of every node
+    mate: Vec<Option<usize>>,
+
+    label: Vec<u8>,
+
+    // Endpoint through which a labelled blossom got its label
+    label_end: Vec<Option<usize>>,
+
+    // Top-level blossom holding every node
+    in_blossom: Vec<usize>,
+    blossom_parent: Vec<Option<usize>>,
+
+    // Sub-blossoms in the order of the cycle, starting at the base
+    blossom_children: Vec<Vec<usize>>,
+    blossom_base: Vec<Option<usize>>,
+
+    // Endpoints connecting consecutive sub-blossoms: blossom_ends[b][i] joins children i and i + 1
+    blossom_ends: Vec<Vec<usize>>,
+
+    // Least slack edge to a different even blossom
+    best_edge: Vec<Option<usize>>,
+
+    // Least slack edges to every other even blossom, for contracted even blossoms
+    blossom_best_edges: Vec<Option<Vec<usize>>>,
+
+    unused_blossoms: Vec<usize>,
+    dual: Vec<W>,
+
+    // Edges with zero slack, allowed in the alternating trees
+    allowed_edge: Vec<bool>,
+    queue: Vec<usize>
+}
+
+impl<W: num_traits::Signed + Copy + Ord> WeightedBlossom<W> {
+
+    // Edges between node indices, self-loops are not allowed
+    pub(crate) fn new(node_count: usize, edges: Vec<(usize, usize, W)>) -> Self {
+        let max_weight = edges.iter().map(|(_, _, weight)| *weight).fold(W::zero(), |max, weight| max.max(weight));
+
+        let mut endpoints = Vec::with_capacity(2 * edges.len());
+        let mut neighbour_ends = vec![Vec::new(); node_count];
+        for (index, (a, b, _)) in edges.iter().enumerate() {
+            endpoints.push(*a);
+            endpoints.push(*b);
+            neighbour_ends[*a].push(2 * index + 1);
+            neighbour_ends[*b].push(2 * index);
+        }
+
+        let mut dual = vec![max_weight; node_count];
+        dual.extend(vec![W::zero(); node_count]);
+
+        WeightedBlossom {
+            node_count,
+            allowed_edge: vec![false; edges.len()],
+            edges,
+            endpoints,
+            neighbour_ends,
+            mate: vec![None; node_count],
+            label: vec![FREE; 2 * node_count],
+            label_end: vec![None; 2 * node_count],
+            in_blossom: (0..node_count).collect(),
+            blossom_parent: vec![None; 2 * node_count],
+            blossom_children: vec![Vec::new(); 2 * node_count],
+            blossom_base: (0..node_count).map(Some).chain(std::iter::repeat_n(None, node_count)).collect(),
+            blossom_ends: vec![Vec::new(); 2 * node_count],
+            best_edge: vec![None; 2 * node_count],
+            blossom_best_edges: vec![None; 2 * node_count],
+            unused_blossoms: (node_count..2 * node_count).collect(),
+            dual,
+            queue: Vec::new()
+        }
+    }
+
+    // Runs the algorithm, returns the matched edge of every node
+    pub(crate) fn solve(mut self) -> Vec<Option<usize>> {
+        let n = self.node_count;
+
+        // Every stage finds one augmenting path or proves there is none
+        for _ in 0..n {
+            self.label.fill(FREE);
+            self.best_edge.fill(None);
+            for best_edges in self.blossom_best_edges[n..].iter_mut() {
+                *best_edges = None;
+            }
+            self.allowed_edge.fill(false);
+            self.queue.clear();
+
+            for node in 0..n {
+                if self.mate[node].is_none() && self.label[self.in_blossom[node]] == FREE {
+                    self.assign_label(node, EVEN, None);
+                }
+            }
+
+            if !self.grow_trees() {
+                break;
+            }
+
+            // Expand even blossoms with zero dual, they are not needed anymore
+            for blossom in n..2 * n {
+                if self.blossom_parent[blossom].is_none() && self.blossom_base[blossom].is_some()
+                    && self.label[blossom] == EVEN && self.dual[blossom] == W::zero() {
+                    self.expand_blossom(blossom, true);
+                }
+            }
+        }
+
+        self.mate.iter().map(|end| end.map(|end| end / 2)).collect()
+    }
+
+    // Grows alternating trees and updates duals until an augmenting path is found (true)
+    // or the duals prove the matching is optimal (false)
+    fn grow_trees(&mut self) -> bool {
+        let n = self.node_count;
+        let two = W::one() + W::one();
+
+        loop {
+            while let Some(node) = self.queue.pop() {
+                for position in 0..self.neighbour_ends[node].len() {
+                    let end = self.neighbour_ends[node][position];
+                    let edge = end / 2;
+                    let neighbour = self.endpoints[end];
+                    if self.in_blossom[node] == self.in_blossom[neighbour] {
+                        continue;
+                    }
+
+                    let mut edge_slack = W::zero();
+                    if !self.allowed_edge[edge] {
+                        edge_slack = self.slack(edge);
+                        if edge_slack <= W::zero() {
+                            self.allowed_edge[edge] = true;
+                        }
+                    }
+
+                    let neighbour_label = self.label[self.in_blossom[neighbour]];
+                    if self.allowed_edge[edge] {
+                        if neighbour_label == FREE {
+                            self.assign_label(neighbour, ODD, Some(end ^ 1));
+                        } else if neighbour_label == EVEN {
+                            match self.scan_blossom(node, neighbour) {
+                                Some(base) => self.add_blossom(base, edge),
+                                None => {
+                                    self.augment_matching(edge);
+                                    return true;
+                                }
+                            }
+                        } else if self.label[neighbour] == FREE {
+                            // Node inside an odd blossom, remember how it can be reached
+                            self.label[neighbour] = ODD;
+                            self.label_end[neighbour] = Some(end ^ 1);
+                        }
+                    } else if neighbour_label == EVEN {
+                        let blossom = self.in_blossom[node];
+                        if self.best_edge[blossom].is_none_or(|best| edge_slack < self.slack(best)) {
+                            self.best_edge[blossom] = Some(edge);
+                        }
+                    } else if self.label[neighbour] == FREE && self.best_edge[neighbour].is_none_or(|best| edge_slack < self.slack(best)) {
+                        self.best_edge[neighbour] = Some(edge);
+                    }
+                }
+            }
+
+            // No progress with tight edges, find the largest dual change that keeps them feasible
+            enum Change { Done, TightenToFree(usize), TightenBetweenEven(usize), ExpandOdd(usize) }
+
+            // Node duals reach zero, the matching is optimal
+            let mut delta = *self.dual[..n].iter().min().unwrap_or(&W::zero());
+            let mut change = Change::Done;
+
+            for node in 0..n {
+                if self.label[self.in_blossom[node]] == FREE {
+                    if let Some(edge) = self.best_edge[node] {
+                        let slack = self.slack(edge);
+                        if slack < delta {
+                            delta = slack;
+                            change = Change::TightenToFree(edge);
+                        }
+                    }
+                }
+            }
+
+            for blossom in 0..2 * n {
+                if self.blossom_parent[blossom].is_none() && self.label[blossom] == EVEN {
+                    if let Some(edge) = self.best_edge[blossom] {
+                        let slack = self.slack(edge) / two;
+                        if slack < delta {
+                            delta = slack;
+                            change = Change::TightenBetweenEven(edge);
+                        }
+                    }
+                }
+            }
+
+            for blossom in n..2 * n {
+                if self.blossom_base[blossom].is_some() && self.blossom_parent[blossom].is_none()
+                    && self.label[blossom] == ODD && self.dual[blossom] < delta {
+                    delta = self.dual[blossom];
+                    change = Change::ExpandOdd(blossom);
+                }
+            }
+
+            for node in 0..n {
+                match self.label[self.in_blossom[node]] {
+                    EVEN => self.dual[node] = self.dual[node] - delta,
+                    ODD => self.dual[node] = self.dual[node] + delta,
+                    _ => ()
+                }
+            }
+            for blossom in n..2 * n {
+                if self.blossom_base[blossom].is_some() && self.blossom_parent[blossom].is_none() {
+                    match self.label[blossom] {
+                        EVEN => self.dual[blossom] = self.dual[blossom] + delta,
+                        ODD => self.dual[blossom] = self.dual[blossom] - delta,
+                        _ => ()
+                    }
+                }
+            }
+
+            match change {
+                Change::Done => return false,
+                Change::TightenToFree(edge) => {
+                    self.allowed_edge[edge] = true;
+                    let (mut a, b, _) = self.edges[edge];
+                    if self.label[self.in_blossom[a]] == FREE {
+                        a = b;
+                    }
+                    self.queue.push(a);
+                },
+                Change::TightenBetweenEven(edge) => {
+                    self.allowed_edge[edge] = true;
+                    self.queue.push(self.edges[edge].0);
+                },
+                Change::ExpandOdd(blossom) => self.expand_blossom(blossom, false)
+            }
+        }
+    }
+
+    fn slack(&self, edge: usize) -> W {
+        let (a, b, weight) = self.edges[edge];
+        self.dual[a] + self.dual[b] - (weight + weight)
+    }
+
+    fn leaves(&self, blossom: usize) -> Vec<usize> {
+        let mut leaves = Vec::new();
+        let mut stack = vec![blossom];
+        while let Some(blossom) = stack.pop() {
+            if blossom < self.node_count {
+                leaves.push(blossom);
+            } else {
+                stack.extend(self.blossom_children[blossom].iter().rev());
+            }
+        }
+        leaves
+    }
+
+    // Labels the top-level blossom of the node, an odd blossom also labels the blossom matched to its base
+    fn assign_label(&mut self, node: usize, label: u8, end: Option<usize>) {
+        let blossom = self.in_blossom[node];
+        self.label[node] = label;
+        self.label[blossom] = label;
+        self.label_end[node] = end;
+        self.label_end[blossom] = end;
+        self.best_edge[node] = None;
+        self.best_edge[blossom] = None;
+
+        if label == EVEN {
+            let leaves = self.leaves(blossom);
+            self.queue.extend(leaves);
+        } else {
+            let base = self.blossom_base[blossom].expect("Blossom has no base");
+            let mate = self.mate[base].expect("Base of an odd blossom is not matched");
+            self.assign_label(self.endpoints[mate], EVEN, Some(mate ^ 1));
+        }
+    }
+
+    // Walks up the trees from two even nodes. Returns the base of the blossom they close,
+    // or None if they are in different trees and form an augmenting path.
+    fn scan_blossom(&mut self, mut a: usize, mut b: usize) -> Option<usize> {
+        let mut path = Vec::new();
+        let mut base = None;
+        let mut b_left = true;
+        loop {
+            let mut blossom = self.in_blossom[a];
+            if self.label[blossom] & BREADCRUMB != 0 {
+                base = self.blossom_base[blossom];
+                break;
+            }
+            path.push(blossom);
+            self.label[blossom] = EVEN | BREADCRUMB;
+
+            let mut a_left = false;
+            if let Some(end) = self.label_end[blossom] {
+                // Skip the odd blossom above
+                blossom = self.in_blossom[self.endpoints[end]];
+                a = self.endpoints[self.label_end[blossom].expect("Odd blossom has no label end")];
+                a_left = true;
+            }
+
+            // Alternate between both paths until both reached their roots
+            if b_left {
+                std::mem::swap(&mut a, &mut b);
+                std::mem::swap(&mut a_left, &mut b_left);
+            }
+            if !a_left {
+                break;
+            }
+        }
+        for blossom in path {
+            self.label[blossom] = EVEN;
+        }
+        base
+    }
+
+    // Contracts the cycle closed by the edge into a new blossom with the given base
+    fn add_blossom(&mut self, base: usize, edge: usize) {
+        let (mut a, mut b, _) = self.edges[edge];
+        let base_blossom = self.in_blossom[base];
+        let mut a_blossom = self.in_blossom[a];
+        let mut b_blossom = self.in_blossom[b];
+
+        let blossom = self.unused_blossoms.pop().expect("No blossom number left");
+        self.blossom_base[blossom] = Some(base);
+        self.blossom_parent[blossom] = None;
+        self.blossom_parent[base_blossom] = Some(blossom);
+
+        let mut children = Vec::new();
+        let mut ends = Vec::new();
+        while a_blossom != base_blossom {
+            self.blossom_parent[a_blossom] = Some(blossom);
+            children.push(a_blossom);
+            let end = self.label_end[a_blossom].expect("Blossom has no label end");
+            ends.push(end);
+            a = self.endpoints[end];
+            a_blossom = self.in_blossom[a];
+        }
+        children.push(base_blossom);
+        children.reverse();
+        ends.reverse();
+        ends.push(2 * edge);
+        while b_blossom != base_blossom {
+            self.blossom_parent[b_blossom] = Some(blossom);
+            children.push(b_blossom);
+            let end = self.label_end[b_blossom].expect("Blossom has no label end");
+            ends.push(end ^ 1);
+            b = self.endpoints[end];
+            b_blossom = self.in_blossom[b];
+        }
+
+        self.label[blossom] = EVEN;
+        self.label_end[blossom] = self.label_end[base_blossom];
+        self.dual[blossom] = W::zero();
+
+        for leaf in self.leaves_of(&children) {
+            if self.label[self.in_blossom[leaf]] == ODD {
+                // Odd nodes become even inside the blossom
+                self.queue.push(leaf);
+            }
+            self.in_blossom[leaf] = blossom;
+        }
+
+        // Least slack edges to every other even blossom
+        let mut best_edge_to: Vec<Option<usize>> = vec![None; 2 * self.node_count];
+        for child in children.iter() {
+            let candidates = match self.blossom_best_edges[*child].take() {
+                Some(best_edges) => best_edges,
+                None => self.leaves(*child)
+                    .into_iter()
+                    .flat_map(|leaf| self.neighbour_ends[leaf].iter().map(|end| end / 2))
+                    .collect()
+            };
+            for candidate in candidates {
+                let (a, b, _) = self.edges[candidate];
+                let other = if self.in_blossom[b] == blossom { self.in_blossom[a] } else { self.in_blossom[b] };
+                if other != blossom && self.label[other] == EVEN
+                    && best_edge_to[other].is_none_or(|best| self.slack(candidate) < self.slack(best)) {
+                    best_edge_to[other] = Some(candidate);
+                }
+            }
+            self.best_edge[*child] = None;
+        }
+
+        let best_edges = best_edge_to.into_iter().flatten().collect::<Vec<usize>>();
+        self.best_edge[blossom] = best_edges.iter().copied().min_by_key(|edge| self.slack(*edge));
+        self.blossom_best_edges[blossom] = Some(best_edges);
+        self.blossom_children[blossom] = children;
+        self.blossom_ends[blossom] = ends;
+    }
+
+    fn leaves_of(&self, blossoms: &[usize]) -> Vec<usize> {
+        blossoms.iter().flat_map(|blossom| self.leaves(*blossom)).collect()
+    }
+
+    // Position in the cycle of sub-blossoms, counted from either direction
+    fn wrap(position: isize, len: usize) -> usize {
+        position.rem_euclid(len as isize) as usize
+    }
+
+    // Undoes a blossom. Outside of the end of a stage an odd blossom is replaced by the even
+    // length part of its cycle, so the alternating tree stays valid.
+    fn expand_blossom(&mut self, blossom: usize, end_of_stage: bool) {
+        let children = self.blossom_children[blossom].clone();
+        for child in children.iter() {
+            self.blossom_parent[*child] = None;
+            if *child < self.node_count {
+                self.in_blossom[*child] = *child;
+            } else if end_of_stage && self.dual[*child] == W::zero() {
+                self.expand_blossom(*child, end_of_stage);
+            } else {
+                for leaf in self.leaves(*child) {
+                    self.in_blossom[leaf] = *child;
+                }
+            }
+        }
+
+        if !end_of_stage && self.label[blossom] == ODD {
+            let len = children.len();
+            let ends = self.blossom_ends[blossom].clone();
+            let label_end = self.label_end[blossom].expect("Odd blossom has no label end");
+            let entry_child = self.in_blossom[self.endpoints[label_end ^ 1]];
+            let mut position = children.iter().position(|child| *child == entry_child).unwrap() as isize;
+
+            // Walk to the base along the even length side of the cycle
+            let (step, end_trick): (isize, usize) = if position & 1 == 1 {
+                position -= len as isize;
+                (1, 0)
+            } else {
+                (-1, 1)
+            };
+
+            let mut end = label_end;
+            while position != 0 {
+                // Relabel the odd sub-blossom and the even one after it
+                self.label[self.endpoints[end ^ 1]] = FREE;
+                let next_end = ends[Self::wrap(position - end_trick as isize, len)];
+                self.label[self.endpoints[next_end ^ end_trick ^ 1]] = FREE;
+                self.assign_label(self.endpoints[end ^ 1], ODD, Some(end));
+                self.allowed_edge[next_end / 2] = true;
+                position += step;
+                end = ends[Self::wrap(position - end_trick as isize, len)] ^ end_trick;
+                self.allowed_edge[end / 2] = true;
+                position += step;
+            }
+
+            // The base sub-blossom takes the label of the whole blossom
+            let base_child = children[Self::wrap(position, len)];
+            let node = self.endpoints[end ^ 1];
+            self.label[node] = ODD;
+            self.label[base_child] = ODD;
+            self.label_end[node] = Some(end);
+            self.label_end[base_child] = Some(end);
+            self.best_edge[base_child] = None;
+
+            // Sub-blossoms on the other side of the cycle become free unless one of their
+            // nodes was reached from outside
+            position += step;
+            while children[Self::wrap(position, len)] != entry_child {
+                let child = children[Self::wrap(position, len)];
+                if self.label[child] == EVEN {
+                    position += step;
+                    continue;
+                }
+                if let Some(leaf) = self.leaves(child).into_iter().find(|leaf| self.label[*leaf] != FREE) {
+                    self.label[leaf] = FREE;
+                    let base = self.blossom_base[child].expect("Blossom has no base");
+                    let mate = self.mate[base].expect("Base of a sub-blossom is not matched");
+                    self.label[self.endpoints[mate]] = FREE;
+                    let leaf_end = self.label_end[leaf];
+                    self.assign_label(leaf, ODD, leaf_end);
+                }
+                position += step;
+            }
+        }
+
+        self.label[blossom] = FREE;
+        self.label_end[blossom] = None;
+        self.blossom_children[blossom] = Vec::new();
+        self.blossom_ends[blossom] = Vec::new();
+        self.blossom_base[blossom] = None;
+        self.blossom_best_edges[blossom] = None;
+        self.best_edge[blossom] = None;
+        self.unused_blossoms.push(blossom);
+    }
+
+    // Swaps matched and unmatched edges on the path from the node to the base of the blossom,
+    // then rotates the blossom so that the node becomes its base
+    fn augment_blossom(&mut self, blossom: usize, node: usize) {
+        let mut child = node;
+        while self.blossom_parent[child] != Some(blossom) {
+            child = self.blossom_parent[child].expect("Node is not inside the blossom");
+        }
+        if child >= self.node_count {
+            self.augment_blossom(child, node);
+        }
+
+        let len = self.blossom_children[blossom].len();
+        let start = self.blossom_children[blossom].iter().position(|other| *other == child).unwrap();
+        let mut position = start as isize;
+        let (step, end_trick): (isize, usize) = if position & 1 == 1 {
+            position -= len as isize;
+            (1, 0)
+        } else {
+            (-1, 1)
+        };
+
+        while position != 0 {
+            position += step;
+            let child = self.blossom_children[blossom][Self::wrap(position, len)];
+            let end = self.blossom_ends[blossom][Self::wrap(position - end_trick as isize, len)] ^ end_trick;
+            if child >= self.node_count {
+                self.augment_blossom(child, self.endpoints[end]);
+            }
+            position += step;
+            let child = self.blossom_children[blossom][Self::wrap(position, len)];
+            if child >= self.node_count {
+                self.augment_blossom(child, self.endpoints[end ^ 1]);
+            }
+            self.mate[self.endpoints[end]] = Some(end ^ 1);
+            self.mate[self.endpoints[end ^ 1]] = Some(end);
+        }
+
+        self.blossom_children[blossom].rotate_left(start);
+        self.blossom_ends[blossom].rotate_left(start);
+        self.blossom_base[blossom] = self.blossom_base[self.blossom_children[blossom][0]];
+    }
+
+    // Flips the augmenting path through the edge between two even nodes of different trees
+    fn augment_matching(&mut self, edge: usize) {
+        let (a, b, _) = self.edges[edge];
+        for (mut node, mut end) in [(a, 2 * edge + 1), (b, 2 * edge)] {
+            loop {
+                let node_blossom = self.in_blossom[node];
+                if node_blossom >= self.node_count {
+                    self.augment_blossom(node_blossom, node);
+                }
+                self.mate[node] = Some(end);
+
+                // Reached the root of the tree
+                let Some(label_end) = self.label_end[node_blossom] else {
+                    break;
+                };
+                let odd_node = self.endpoints[label_end];
+                let odd_blossom = self.in_blossom[odd_node];
+                let odd_end = self.label_end[odd_blossom].expect("Odd blossom has no label end");
+                node = self.endpoints[odd_end];
+                let other = self.endpoints[odd_end ^ 1];
+                if odd_blossom >= self.node_count {
+                    self.augment_blossom(odd_blossom, other);
+                }
+                self.mate[other] = Some(odd_end);
+                end = odd_end ^ 1;
+            }
+        }
+    }
+}
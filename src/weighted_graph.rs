@@ -5,7 +5,7 @@ use std::{fmt, hash::Hash};
 
 use crate::directed::Directed;
 use crate::{graph_trait::*};
-use crate::path_finder::PathFindable;
+use crate::path_finder::{PathFindable, NodeIndexable, EdgeIndexable};
 use crate::undirected::Undirected;
 use std::iter::{Iterator};
 
@@ -70,6 +70,11 @@ impl<T, W> WeightedGraph<T, W> {
         self.graph.len()
     }
 
+    // Number of edges, an undirected edge counts once
+    pub fn edge_count(&self) -> usize {
+        self.edge_count
+    }
+
     // Get a vector of neighbouring nodes
     pub fn get_neighbours(&self, node: Node) -> EdgeIter<W> {
         EdgeIter { iterator: self.graph.get_neighbours(node.uid), weights: self.weights.as_ptr() }
@@ -82,6 +87,41 @@ impl<T, W> WeightedGraph<T, W> {
     pub fn get_weight(&self, edge: Edge) -> &W {
         &self.weights[edge.uid]
     }
+
+    pub fn is_directed(&self) -> bool {
+        self.graph.is_directed()
+    }
+}
+
+impl<T: Clone, W: Clone> WeightedGraph<T, W> {
+    // Copy of the given nodes and of the edges between them, nodes keep the given order.
+    // The copy uses the built-in directed or undirected storage.
+    // O(V + E)
+    pub fn induced_subgraph(&self, nodes: &[Node]) -> Self {
+        let mut subgraph = if self.is_directed() { WeightedGraph::new_directed() } else { WeightedGraph::new_undirected() };
+
+        let mut new_nodes: Vec<Option<Node>> = vec![None; self.len()];
+        for node in nodes {
+            if new_nodes[node.uid].is_none() {
+                new_nodes[node.uid] = Some(subgraph.add_node(self.values[node.uid].clone()));
+            }
+        }
+
+        // Undirected edges are listed by both of their nodes
+        let mut copied = vec![false; self.edge_count];
+        for (edge, weight) in self.edges() {
+            if copied[edge.uid] {
+                continue;
+            }
+            copied[edge.uid] = true;
+
+            if let (Some(source), Some(target)) = (new_nodes[edge.source.uid], new_nodes[edge.target.uid]) {
+                subgraph.add_edge(source, target, weight.clone());
+            }
+        }
+
+        subgraph
+    }
 }
 
 impl<T: Copy, W> WeightedGraph<T, W> {
@@ -332,3 +372,35 @@ impl<'a, T, W: Copy> PathFindable<'a, Node, W> for WeightedGraph<T, W> {
         Box::new(self.get_neighbours(n).map(|edge| (edge.0.target, *edge.1)))
     }
 }
+
+impl<T, W> NodeIndexable<Node> for WeightedGraph<T, W> {
+    fn node_count(&self) -> usize {
+        self.len()
+    }
+
+    fn index_of(&self, n: Node) -> usize {
+        n.uid
+    }
+
+    fn node_at(&self, index: usize) -> Node {
+        Node::from(index)
+    }
+}
+
+impl<'a, T, W: Copy> EdgeIndexable<'a, Node, Edge, W> for WeightedGraph<T, W> {
+    fn edge_count(&self) -> usize {
+        self.edge_count
+    }
+
+    fn edge_index(&self, e: Edge) -> usize {
+        e.uid
+    }
+
+    fn is_directed(&self) -> bool {
+        self.is_directed()
+    }
+
+    fn get_edges(&'a self, n: Node) -> Box<dyn Iterator<Item=(Edge, Node, W)> + 'a> {
+        Box::new(self.get_neighbours(n).map(|(edge, weight)| (edge, edge.target, *weight)))
+    }
+}
@@ -0,0 +1,276 @@
+use grapher::centrality::{Centrality, BetweennessSources};
+use grapher::graph::Graph;
+use grapher::node_map::NodeMap;
+use grapher::weighted_graph::WeightedGraph;
+
+fn assert_scores<T: Copy + PartialEq>(graph: &Graph<T>, scores: &NodeMap<f64>, expected: &[(T, f64)], precision: f64) {
+    for (value, score) in expected {
+        let node = graph.find_node_with_value(value).unwrap();
+        assert!((scores[node] - score).abs() < precision, "score {} instead of {}", scores[node], score);
+    }
+}
+
+#[test]
+fn pagerank()
+{
+    // 3 has no incoming edges and 4 is dangling
+    let g = Graph::from([(0,1), (0,2), (1,2), (2,0), (3,2), (2,4)]);
+    let scores = Centrality::pagerank(&g, 0.85, 1e-12, 1000);
+
+    assert_eq!(5, scores.len());
+    assert!((scores.values().iter().sum::<f64>() - 1.0).abs() < 1e-9);
+    assert_scores(&g, &scores, &[(0, 0.214201), (1, 0.157450), (2, 0.347734), (3, 0.066414), (4, 0.214201)], 1e-6);
+}
+
+#[test]
+fn pagerank_of_cycle_is_uniform()
+{
+    let g = Graph::from([(0,1), (1,2), (2,3), (3,0)]);
+    let scores = Centrality::pagerank(&g, 0.85, 1e-12, 100);
+    assert!(scores.values().iter().all(|score| (score - 0.25).abs() < 1e-9));
+}
+
+#[test]
+fn pagerank_of_undirected_graph_follows_degrees()
+{
+    // Without random jumps the walk settles in proportion to degrees
+    let g = Graph::from_vec_undirected(vec![(0,1), (1,2), (2,0), (2,3)]);
+    let scores = Centrality::pagerank(&g, 1.0, 1e-12, 1000);
+    assert_scores(&g, &scores, &[(0, 2.0 / 8.0), (1, 2.0 / 8.0), (2, 3.0 / 8.0), (3, 1.0 / 8.0)], 1e-9);
+}
+
+#[test]
+fn weighted_pagerank()
+{
+    // 0 sends three times more to 1 than to 2
+    let g = WeightedGraph::from([(0,1,3), (0,2,1), (1,0,1), (2,0,1)]);
+    let scores = Centrality::pagerank(&g, 0.85, 1e-12, 1000);
+    let node = |value| g.find_node_with_value(&value).unwrap();
+
+    let first = 0.135 / 0.2775;
+    assert!((scores[node(0)] - first).abs() < 1e-9);
+    assert!((scores[node(1)] - (0.85 * 0.75 * first + 0.05)).abs() < 1e-9);
+    assert!((scores[node(2)] - (0.85 * 0.25 * first + 0.05)).abs() < 1e-9);
+}
+
+#[test]
+fn pagerank_stops_after_max_iter()
+{
+    let g = Graph::from([(0,1), (1,1)]);
+    let scores = Centrality::pagerank(&g, 0.85, 0.0, 1);
+
+    // One step from the uniform start
+    assert_scores(&g, &scores, &[(0, 0.075), (1, 0.925)], 1e-9);
+}
+
+#[test]
+fn personalized_pagerank()
+{
+    let g = Graph::from_vec_undirected(vec![(0,1), (1,2), (2,3), (3,4)]);
+    let node = |value| g.find_node_with_value(&value).unwrap();
+    let scores = Centrality::personalized_pagerank(&g, 0.85, 1e-12, 1000, &[(node(0), 2.0)]);
+
+    assert!((scores.values().iter().sum::<f64>() - 1.0).abs() < 1e-9);
+    // Scores fall with the distance from 0
+    for value in 2..5 {
+        assert!(scores[node(value)] < scores[node(value - 1)]);
+    }
+    assert!(scores[node(0)] > scores[node(2)]);
+}
+
+#[test]
+fn personalized_pagerank_sends_dangling_nodes_to_personalisation()
+{
+    // Everything ends up in 1, either by jumping or by following the edge from 0
+    let g = Graph::from([(0,1), (2,2)]);
+    let node = |value| g.find_node_with_value(&value).unwrap();
+    let scores = Centrality::personalized_pagerank(&g, 0.5, 1e-12, 1000, &[(node(0), 1.0)]);
+
+    assert!((scores[node(0)] - 2.0 / 3.0).abs() < 1e-9);
+    assert!((scores[node(1)] - 1.0 / 3.0).abs() < 1e-9);
+    assert!(scores[node(2)].abs() < 1e-9);
+}
+
+#[test]
+#[should_panic]
+fn personalized_pagerank_without_personalisation()
+{
+    let g = Graph::from([(0,1)]);
+    Centrality::personalized_pagerank(&g, 0.85, 1e-9, 100, &[]);
+}
+
+#[test]
+#[should_panic]
+fn pagerank_with_invalid_damping()
+{
+    let g: Graph<usize> = Graph::new_directed();
+    Centrality::pagerank(&g, 1.5, 1e-9, 100);
+}
+
+fn assert_close(expected: &[f64], actual: &[f64]) {
+    assert_eq!(expected.len(), actual.len());
+    for (expected, actual) in expected.iter().zip(actual) {
+        assert!((expected - actual).abs() < 1e-9, "{:?} instead of {:?}", actual, expected);
+    }
+}
+
+#[test]
+fn betweenness_of_path()
+{
+    let g = Graph::from_vec_undirected(vec![(0,1), (1,2), (2,3), (3,4)]);
+    let betweenness = Centrality::betweenness(&g, BetweennessSources::All);
+
+    assert_close(&[0.0, 3.0, 4.0, 3.0, 0.0], betweenness.nodes.values());
+    assert_close(&[4.0, 6.0, 6.0, 4.0], betweenness.edges.values());
+}
+
+#[test]
+fn betweenness_of_directed_graph()
+{
+    // Two shortest paths from 0 to 3, only 1 lies on a path from 4
+    let g = Graph::from([(0,1), (0,2), (1,3), (2,3), (4,1)]);
+    let betweenness = Centrality::betweenness(&g, BetweennessSources::All);
+    let node = |value| g.find_node_with_value(&value).unwrap();
+
+    assert!((betweenness.nodes[node(1)] - 1.5).abs() < 1e-9);
+    assert!((betweenness.nodes[node(2)] - 0.5).abs() < 1e-9);
+    assert_eq!(0.0, betweenness.nodes[node(0)]);
+    for edge in g.edges() {
+        let expected = match g.get_edge_values(edge) {
+            (0,1) | (0,2) => 1.5,
+            (1,3) => 2.5,
+            (2,3) => 1.5,
+            _ => 2.0
+        };
+        assert!((betweenness.edges[edge] - expected).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn weighted_betweenness()
+{
+    let g = WeightedGraph::from_vec_undirected(vec![('a','b',1), ('b','c',1), ('a','c',3)]);
+    let betweenness = Centrality::weighted_betweenness(&g, BetweennessSources::All);
+    assert_eq!(1.0, betweenness.nodes[g.find_node_with_value(&'b').unwrap()]);
+
+    // Equally long paths share the pair
+    let g = WeightedGraph::from_vec_undirected(vec![('a','b',1), ('b','c',1), ('a','c',2)]);
+    let betweenness = Centrality::weighted_betweenness(&g, BetweennessSources::All);
+    assert_eq!(0.5, betweenness.nodes[g.find_node_with_value(&'b').unwrap()]);
+}
+
+#[test]
+fn sampled_betweenness()
+{
+    let g = Graph::from_vec_undirected(vec![(0,1), (1,2), (2,3), (3,4), (4,0), (1,3)]);
+    let exact = Centrality::betweenness(&g, BetweennessSources::All);
+
+    // Sampling every node gives exact values
+    let sampled = Centrality::betweenness(&g, BetweennessSources::Sample { count: 5, seed: 7 });
+    assert_close(exact.nodes.values(), sampled.nodes.values());
+
+    // Same seed, same estimate
+    let estimate = Centrality::betweenness(&g, BetweennessSources::Sample { count: 2, seed: 7 });
+    assert_eq!(estimate, Centrality::betweenness(&g, BetweennessSources::Sample { count: 2, seed: 7 }));
+}
+
+#[test]
+fn betweenness_written_back_into_nodes()
+{
+    let mut g = WeightedGraph::new_undirected();
+    let nodes = (0..3).map(|_| g.add_node(0.0)).collect::<Vec<_>>();
+    g.add_edge(nodes[0], nodes[1], ());
+    g.add_edge(nodes[1], nodes[2], ());
+    let betweenness = Centrality::betweenness(&g, BetweennessSources::All);
+    for (node, value) in g.nodes_mut() {
+        *value = betweenness.nodes[node];
+    }
+    assert_eq!(vec![0.0, 1.0, 0.0], g.nodes().map(|(_, value)| *value).collect::<Vec<_>>());
+}
+
+#[test]
+fn closeness()
+{
+    let g = Graph::from_vec_undirected(vec![(0,1), (1,2), (3,3)]);
+    let closeness = Centrality::closeness(&g);
+
+    // Only two of three other nodes are reachable
+    assert_close(&[2.0 / 3.0 * 2.0 / 3.0, 2.0 / 3.0, 2.0 / 3.0 * 2.0 / 3.0, 0.0], closeness.values());
+}
+
+#[test]
+fn harmonic()
+{
+    let g = Graph::from([(0,1), (1,2), (2,0), (0,3)]);
+    let harmonic = Centrality::harmonic(&g);
+    assert_close(&[1.0 + 0.5 + 1.0, 1.0 + 0.5 + 1.0 / 3.0, 1.0 + 0.5 + 0.5, 0.0], harmonic.values());
+}
+
+#[test]
+fn eigenvector_of_star()
+{
+    let g = Graph::from_vec_undirected(vec![(0,1), (0,2), (0,3)]);
+    let result = Centrality::eigenvector(&g, 1e-12, 1000);
+
+    assert!(result.iterations < 1000);
+    assert!(result.residual < 1e-12);
+    let leaf = 1.0 / 6.0f64.sqrt();
+    assert_close(&[3.0f64.sqrt() * leaf, leaf, leaf, leaf], result.scores.values());
+}
+
+#[test]
+fn eigenvector_reports_unfinished_iteration()
+{
+    let g = Graph::from_vec_undirected(vec![(0,1), (1,2), (2,3), (3,4), (4,0), (0,2)]);
+    let result = Centrality::eigenvector(&g, 1e-12, 3);
+
+    assert_eq!(3, result.iterations);
+    assert!(result.residual > 1e-12);
+}
+
+#[test]
+fn katz()
+{
+    // On a directed path Katz converges exactly after the path length
+    let g = Graph::from([(0,1), (1,2)]);
+    let result = Centrality::katz(&g, 0.5, 1.0, 1e-12, 100);
+
+    assert_eq!(4, result.iterations);
+    assert_eq!(0.0, result.residual);
+    let length = (1.0f64 + 1.5 * 1.5 + 1.75 * 1.75).sqrt();
+    assert_close(&[1.0 / length, 1.5 / length, 1.75 / length], result.scores.values());
+}
+
+#[test]
+fn weighted_katz()
+{
+    let g = WeightedGraph::from([(0,1,2), (2,1,1)]);
+    let result = Centrality::katz(&g, 0.1, 1.0, 1e-12, 100);
+    let length = (1.0f64 + 1.3 * 1.3 + 1.0).sqrt();
+    assert_close(&[1.0 / length, 1.3 / length, 1.0 / length], result.scores.values());
+}
+
+#[test]
+fn hits()
+{
+    let g = Graph::from([(0,2), (0,3), (1,2)]);
+    let result = Centrality::hits(&g, 1e-12, 1000);
+    let node = |value| g.find_node_with_value(&value).unwrap();
+    let golden_ratio = (1.0 + 5.0f64.sqrt()) / 2.0;
+
+    assert!(result.residual < 1e-12);
+    assert!((result.hubs.values().iter().sum::<f64>() - 1.0).abs() < 1e-9);
+    assert!((result.authorities.values().iter().sum::<f64>() - 1.0).abs() < 1e-9);
+    assert!((result.hubs[node(0)] / result.hubs[node(1)] - golden_ratio).abs() < 1e-6);
+    assert!((result.authorities[node(2)] / result.authorities[node(3)] - golden_ratio).abs() < 1e-6);
+    assert_eq!(0.0, result.hubs[node(2)]);
+    assert_eq!(0.0, result.authorities[node(0)]);
+}
+
+#[test]
+fn hits_of_undirected_graph()
+{
+    let g = Graph::from_vec_undirected(vec![(0,1), (1,2), (2,0), (2,3)]);
+    let result = Centrality::hits(&g, 1e-12, 1000);
+    assert_close(result.hubs.values(), result.authorities.values());
+}
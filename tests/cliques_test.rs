@@ -0,0 +1,96 @@
+use std::time::Duration;
+
+use grapher::cliques::Cliques;
+use grapher::graph::Graph;
+use grapher::weighted_graph::{Node, WeightedGraph};
+
+// Values of nodes of every clique, sorted
+fn clique_values(graph: &Graph<usize>, cliques: impl Iterator<Item = Vec<Node>>) -> Vec<Vec<usize>> {
+    let mut values: Vec<Vec<usize>> = cliques
+        .map(|clique| {
+            let mut values: Vec<usize> = clique.iter().map(|node| graph[*node]).collect();
+            values.sort();
+            values
+        })
+        .collect();
+    values.sort();
+    values
+}
+
+#[test]
+fn maximal_cliques()
+{
+    let mut g = Graph::from_vec_undirected(vec![(0,1), (1,2), (2,0), (1,3), (2,3), (3,4), (4,4), (3,4)]);
+    g.add_node(5);
+
+    let cliques = clique_values(&g, Cliques::maximal_cliques(&g));
+    assert_eq!(vec![vec![0, 1, 2], vec![1, 2, 3], vec![3, 4], vec![5]], cliques);
+}
+
+#[test]
+fn complete_graph_is_one_clique()
+{
+    let mut edges = vec![];
+    for i in 0..6 {
+        for j in i + 1..6 {
+            edges.push((i, j, 1));
+        }
+    }
+    let g = WeightedGraph::from_vec_undirected(edges);
+
+    let cliques: Vec<_> = Cliques::maximal_cliques(&g).collect();
+    assert_eq!(1, cliques.len());
+    assert_eq!(6, cliques[0].len());
+}
+
+#[test]
+fn cliques_are_listed_lazily()
+{
+    // Every node of a cycle of 1000 nodes forms a clique with the next one
+    let g = Graph::from_vec_undirected((0..1000).map(|i| (i, (i + 1) % 1000)).collect());
+
+    let mut cliques = Cliques::maximal_cliques(&g);
+    assert_eq!(2, cliques.next().unwrap().len());
+    assert_eq!(999, cliques.count());
+}
+
+#[test]
+fn maximum_clique()
+{
+    let mut edges = vec![(0,10), (10,11), (11,12), (12,0), (0,11)];
+    for i in 1..6 {
+        for j in i + 1..6 {
+            edges.push((i, j));
+        }
+    }
+    edges.push((5, 10));
+    let g = Graph::from_vec_undirected(edges);
+
+    let clique = Cliques::maximum_clique(&g, None);
+    assert!(clique.is_optimal);
+    assert_eq!(vec![vec![1, 2, 3, 4, 5]], clique_values(&g, std::iter::once(clique.nodes)));
+
+    let within_budget = Cliques::maximum_clique(&g, Some(Duration::from_secs(60)));
+    assert!(within_budget.is_optimal);
+    assert_eq!(5, within_budget.nodes.len());
+}
+
+#[test]
+fn maximum_clique_out_of_time()
+{
+    let g = Graph::from_vec_undirected(vec![(0,1), (1,2), (2,0)]);
+    let clique = Cliques::maximum_clique(&g, Some(Duration::ZERO));
+    assert!(!clique.is_optimal);
+
+    let empty: Graph<usize> = Graph::new_undirected();
+    let clique = Cliques::maximum_clique(&empty, None);
+    assert!(clique.is_optimal);
+    assert!(clique.nodes.is_empty());
+}
+
+#[test]
+#[should_panic]
+fn directed_graph()
+{
+    Cliques::maximal_cliques(&Graph::from([(0,1), (1,2), (2,0)]));
+}
@@ -0,0 +1,122 @@
+use std::collections::BTreeSet;
+use std::fs::File;
+use std::io::BufReader;
+use std::io::prelude::*;
+
+use grapher::clustering::Clustering;
+use grapher::graph::Graph;
+use grapher::path_finder::NodeIndexable;
+use grapher::weighted_graph::WeightedGraph;
+
+fn assert_close(expected: &[f64], actual: &[f64]) {
+    assert_eq!(expected.len(), actual.len());
+    for (expected, actual) in expected.iter().zip(actual) {
+        assert!((expected - actual).abs() < 1e-9, "{:?} instead of {:?}", actual, expected);
+    }
+}
+
+#[test]
+fn two_triangles()
+{
+    let g = Graph::from_vec_undirected(vec![(0,1), (1,2), (2,0), (2,3), (3,4), (4,2)]);
+
+    let triangles = Clustering::triangles(&g);
+    assert_eq!(2, triangles.total);
+    assert_eq!(&[1, 1, 2, 1, 1], triangles.nodes.values());
+
+    assert_close(&[1.0, 1.0, 1.0 / 3.0, 1.0, 1.0], Clustering::local(&g).values());
+    assert!((Clustering::average(&g) - 13.0 / 15.0).abs() < 1e-9);
+    assert!((Clustering::transitivity(&g) - 0.6).abs() < 1e-9);
+}
+
+#[test]
+fn complete_graph()
+{
+    let mut edges = vec![];
+    for i in 0..6 {
+        for j in i + 1..6 {
+            edges.push((i, j, i * j));
+        }
+    }
+    let g = WeightedGraph::from_vec_undirected(edges);
+
+    let triangles = Clustering::triangles(&g);
+    assert_eq!(20, triangles.total);
+    assert!(triangles.nodes.values().iter().all(|&count| count == 10));
+    assert_close(&[1.0; 6], Clustering::local(&g).values());
+    assert_eq!(1.0, Clustering::transitivity(&g));
+}
+
+#[test]
+fn self_loops_and_parallel_edges_are_ignored()
+{
+    let g = Graph::from_vec_undirected(vec![(0,1), (1,0), (1,2), (2,0), (0,0), (2,3)]);
+
+    let triangles = Clustering::triangles(&g);
+    assert_eq!(1, triangles.total);
+    assert_eq!(&[1, 1, 1, 0], triangles.nodes.values());
+    assert_close(&[1.0, 1.0, 1.0 / 3.0, 0.0], Clustering::local(&g).values());
+    assert!((Clustering::transitivity(&g) - 3.0 / 5.0).abs() < 1e-9);
+}
+
+#[test]
+fn no_triangles()
+{
+    let g = Graph::from_vec_undirected(vec![(0,1), (1,2), (2,3), (3,0)]);
+    assert_eq!(0, Clustering::triangles(&g).total);
+    assert_eq!(0.0, Clustering::average(&g));
+    assert_eq!(0.0, Clustering::transitivity(&g));
+
+    let empty: Graph<usize> = Graph::new_undirected();
+    assert_eq!(0.0, Clustering::average(&empty));
+    assert_eq!(0.0, Clustering::transitivity(&empty));
+}
+
+#[test]
+#[should_panic]
+fn directed_graph()
+{
+    Clustering::triangles(&Graph::from([(0,1), (1,2), (2,0)]));
+}
+
+#[test]
+fn facebook_triangles()
+{
+    let file = File::open("tests/data/facebook_test_data.txt").expect("Can't open data");
+    let edges: Vec<(usize, usize)> = BufReader::new(file).lines()
+        .map(|line| {
+            let nodes: Vec<usize> = line.expect("Can't read line")
+                .split_whitespace()
+                .map(|n| n.parse().expect("Can't parse node"))
+                .collect();
+            (nodes[0], nodes[1])
+        })
+        .collect();
+    let g = Graph::from_vec_undirected(edges);
+
+    // Count every triangle from its lowest node by intersecting neighbour sets
+    let mut neighbours = vec![BTreeSet::new(); g.node_count()];
+    for edge in g.edges() {
+        let (source, target) = (g.index_of(edge.source), g.index_of(edge.target));
+        if source != target {
+            neighbours[source].insert(target);
+            neighbours[target].insert(source);
+        }
+    }
+    let mut expected = vec![0; g.node_count()];
+    let mut expected_total = 0;
+    for first in 0..neighbours.len() {
+        for &second in neighbours[first].range(first + 1..) {
+            for &third in neighbours[first].intersection(&neighbours[second]).filter(|&&third| third > second) {
+                expected[first] += 1;
+                expected[second] += 1;
+                expected[third] += 1;
+                expected_total += 1;
+            }
+        }
+    }
+
+    let triangles = Clustering::triangles(&g);
+    assert_eq!(expected_total, triangles.total);
+    assert_eq!(&expected[..], triangles.nodes.values());
+}
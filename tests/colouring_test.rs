@@ -0,0 +1,142 @@
+use std::fs::File;
+use std::io::BufReader;
+use std::io::prelude::*;
+
+use grapher::colouring::{Colouring, ColouringOrder, VertexColouring};
+use grapher::graph::Graph;
+use grapher::weighted_graph::WeightedGraph;
+
+const ORDERS: [ColouringOrder; 3] = [ColouringOrder::LargestFirst, ColouringOrder::SmallestLast, ColouringOrder::DSatur];
+
+fn assert_proper<T>(graph: &Graph<T>, colouring: &VertexColouring) {
+    assert_eq!(graph.len(), colouring.colours.len());
+    for edge in graph.edges() {
+        if edge.source != edge.target {
+            assert_ne!(colouring.colours[edge.source], colouring.colours[edge.target]);
+        }
+    }
+    assert!(colouring.colours.values().iter().all(|&colour| colour < colouring.count));
+}
+
+fn petersen() -> Graph<usize> {
+    let mut edges = vec![];
+    for i in 0..5 {
+        edges.push((i, (i + 1) % 5));
+        edges.push((i, i + 5));
+        edges.push((i + 5, (i + 2) % 5 + 5));
+    }
+    Graph::from_vec_undirected(edges)
+}
+
+#[test]
+fn greedy_orders()
+{
+    let g = petersen();
+    for order in ORDERS {
+        let colouring = Colouring::greedy(&g, order);
+        assert_proper(&g, &colouring);
+        assert!(colouring.count <= 4);
+    }
+}
+
+#[test]
+fn dsatur_colours_bipartite_graphs_with_two_colours()
+{
+    // Complete bipartite graph without a perfect matching, bad for colouring in order of values
+    let mut edges = vec![];
+    for i in 0..6 {
+        for j in 0..6 {
+            if i != j {
+                edges.push((2 * i, 2 * j + 1));
+            }
+        }
+    }
+    let g = Graph::from_vec_undirected(edges);
+
+    let colouring = Colouring::greedy(&g, ColouringOrder::DSatur);
+    assert_proper(&g, &colouring);
+    assert_eq!(2, colouring.count);
+}
+
+#[test]
+fn smallest_last_colours_trees_with_two_colours()
+{
+    let g = Graph::from_vec_undirected(vec![(0,1), (0,2), (1,3), (1,4), (2,5), (5,6), (6,7), (0,0)]);
+
+    let colouring = Colouring::greedy(&g, ColouringOrder::SmallestLast);
+    assert_proper(&g, &colouring);
+    assert_eq!(2, colouring.count);
+}
+
+#[test]
+fn k_colouring()
+{
+    let cycle = Graph::from_vec_undirected(vec![(0,1), (1,2), (2,3), (3,4), (4,0)]);
+    assert_eq!(None, Colouring::k_colouring(&cycle, 2));
+    let colouring = Colouring::k_colouring(&cycle, 3).unwrap();
+    assert_proper(&cycle, &colouring);
+    assert_eq!(3, colouring.count);
+
+    let g = petersen();
+    assert_eq!(None, Colouring::k_colouring(&g, 2));
+    let colouring = Colouring::k_colouring(&g, 4).unwrap();
+    assert_proper(&g, &colouring);
+    assert_eq!(3, colouring.count);
+}
+
+#[test]
+fn k_colouring_of_weighted_graph()
+{
+    let mut edges = vec![];
+    for i in 0..5 {
+        for j in i + 1..5 {
+            edges.push((i, j, 1.5));
+        }
+    }
+    let g = WeightedGraph::from_vec_undirected(edges);
+
+    assert_eq!(None, Colouring::k_colouring(&g, 4));
+    assert_eq!(5, Colouring::k_colouring(&g, 5).unwrap().count);
+}
+
+#[test]
+fn empty_graph()
+{
+    let g: Graph<usize> = Graph::new_undirected();
+    assert_eq!(0, Colouring::greedy(&g, ColouringOrder::DSatur).count);
+    assert_eq!(0, Colouring::k_colouring(&g, 0).unwrap().count);
+}
+
+#[test]
+#[should_panic]
+fn directed_graph()
+{
+    Colouring::greedy(&Graph::from([(0,1), (1,2)]), ColouringOrder::LargestFirst);
+}
+
+#[test]
+fn facebook_colouring()
+{
+    let file = File::open("tests/data/facebook_test_data.txt").expect("Can't open data");
+    let edges: Vec<(usize, usize)> = BufReader::new(file).lines()
+        .map(|line| {
+            let nodes: Vec<usize> = line.expect("Can't read line")
+                .split_whitespace()
+                .map(|n| n.parse().expect("Can't parse node"))
+                .collect();
+            (nodes[0], nodes[1])
+        })
+        .collect();
+    let g = Graph::from_vec_undirected(edges);
+
+    for order in ORDERS {
+        let colouring = Colouring::greedy(&g, order);
+        assert_proper(&g, &colouring);
+
+        // Degeneracy is 53 and the largest clique has 17 nodes
+        assert!(colouring.count >= 17);
+        if order == ColouringOrder::SmallestLast {
+            assert!(colouring.count <= 54);
+        }
+    }
+}
@@ -0,0 +1,119 @@
+use grapher::community::{Community, Communities};
+use grapher::graph::Graph;
+use grapher::weighted_graph::WeightedGraph;
+
+// Complete graphs of `size` nodes, every one connected to the next one by a single edge
+fn ring_of_cliques(count: usize, size: usize) -> Graph<usize> {
+    let mut edges = vec![];
+    for clique in 0..count {
+        let first = clique * size;
+        for i in first..first + size {
+            for j in i + 1..first + size {
+                edges.push((i, j));
+            }
+        }
+        edges.push((first, (first + size) % (count * size)));
+    }
+    Graph::from_vec_undirected(edges)
+}
+
+// Labels of nodes with the given values, in that order
+fn labels_of(graph: &Graph<usize>, communities: &Communities, values: &[usize]) -> Vec<usize> {
+    values.iter().map(|value| communities.labels[graph.find_node_with_value(value).unwrap()]).collect()
+}
+
+#[test]
+fn modularity()
+{
+    let g = Graph::from_vec_undirected(vec![(0,1), (1,2), (2,0), (3,4), (4,5), (5,3), (2,3)]);
+    let two_triangles = Community::louvain(&g, 1.0, 7);
+    assert_eq!(2, two_triangles.count);
+    assert!((two_triangles.modularity - (6.0 / 7.0 - 0.5)).abs() < 1e-9);
+    assert!((Community::modularity(&g, &two_triangles.labels, 1.0) - two_triangles.modularity).abs() < 1e-9);
+
+    let single = Community::label_propagation(&Graph::from_vec_undirected(vec![(0,1)]), 0);
+    assert_eq!(1, single.count);
+    assert!((single.modularity - 0.0).abs() < 1e-9);
+}
+
+#[test]
+fn louvain_ring_of_cliques()
+{
+    let g = ring_of_cliques(6, 5);
+    let communities = Community::louvain(&g, 1.0, 42);
+
+    assert_eq!(6, communities.count);
+    for clique in 0..6 {
+        let labels = labels_of(&g, &communities, &(clique * 5..clique * 5 + 5).collect::<Vec<_>>());
+        assert!(labels.iter().all(|&label| label == labels[0]));
+    }
+    assert!((Community::modularity(&g, &communities.labels, 1.0) - communities.modularity).abs() < 1e-9);
+    assert_eq!(communities, Community::louvain(&g, 1.0, 42));
+}
+
+#[test]
+fn louvain_resolution()
+{
+    let g = ring_of_cliques(6, 5);
+    assert_eq!(1, Community::louvain(&g, 0.0, 1).count);
+    assert_eq!(30, Community::louvain(&g, 100.0, 1).count);
+}
+
+#[test]
+fn weighted_louvain()
+{
+    let g = WeightedGraph::from_vec_undirected(vec![(0,1,10), (1,2,1), (2,3,10), (3,0,1)]);
+    let communities = Community::louvain(&g, 1.0, 3);
+
+    assert_eq!(2, communities.count);
+    let labels = communities.labels.values();
+    assert_eq!(labels[0], labels[1]);
+    assert_eq!(labels[2], labels[3]);
+    assert_ne!(labels[0], labels[2]);
+    assert!((communities.modularity - (20.0 / 22.0 - 0.5)).abs() < 1e-9);
+}
+
+#[test]
+fn graph_without_edges()
+{
+    let mut g = Graph::new_undirected();
+    for value in 0..3 {
+        g.add_node(value);
+    }
+
+    let communities = Community::louvain(&g, 1.0, 0);
+    assert_eq!(3, communities.count);
+    assert_eq!(&[0, 1, 2], communities.labels.values());
+    assert_eq!(0.0, communities.modularity);
+    assert_eq!(3, Community::label_propagation(&g, 0).count);
+}
+
+#[test]
+fn label_propagation()
+{
+    // Two separate cliques
+    let mut edges = vec![];
+    for first in [0, 10] {
+        for i in first..first + 5 {
+            for j in i + 1..first + 5 {
+                edges.push((i, j));
+            }
+        }
+    }
+    let g = Graph::from_vec_undirected(edges);
+    let communities = Community::label_propagation(&g, 5);
+
+    assert_eq!(2, communities.count);
+    let labels = labels_of(&g, &communities, &[0, 1, 2, 3, 4, 10, 11, 12, 13, 14]);
+    assert!(labels[..5].iter().all(|&label| label == labels[0]));
+    assert!(labels[5..].iter().all(|&label| label == labels[5]));
+    assert!((communities.modularity - 0.5).abs() < 1e-9);
+    assert_eq!(communities, Community::label_propagation(&g, 5));
+}
+
+#[test]
+#[should_panic]
+fn directed_graph()
+{
+    Community::louvain(&Graph::from([(0,1), (1,2)]), 1.0, 0);
+}
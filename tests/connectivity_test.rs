@@ -0,0 +1,267 @@
+use grapher::connectivity::Connectivity;
+use grapher::graph::{Graph, Node};
+use grapher::weighted_graph::WeightedGraph;
+
+fn values<T: Copy>(graph: &Graph<T>, nodes: &[Node]) -> Vec<T> {
+    nodes.iter().map(|node| graph[*node]).collect()
+}
+
+#[test]
+fn connected_components()
+{
+    let g = Graph::from_vec_undirected(vec![(0,1), (1,2), (3,4), (5,5), (2,0)]);
+    let components = Connectivity::connected_components(&g);
+
+    assert_eq!(3, components.count());
+    let members = components.members();
+    assert_eq!(vec![0, 1, 2], values(&g, &members[0]));
+    assert_eq!(vec![3, 4], values(&g, &members[1]));
+    assert_eq!(vec![5], values(&g, &members[2]));
+
+    let node = |value| g.find_node_with_value(&value).unwrap();
+    assert_eq!(components[node(0)], components.component_of(node(2)));
+    assert_ne!(components[node(0)], components[node(3)]);
+}
+
+#[test]
+fn connected_components_of_isolated_nodes()
+{
+    let mut g: Graph<usize> = Graph::new_undirected();
+    for value in 0..4 {
+        g.add_node(value);
+    }
+    let components = Connectivity::connected_components(&g);
+    assert_eq!(4, components.count());
+    assert_eq!(&[0, 1, 2, 3], components.as_node_map().values());
+}
+
+#[test]
+fn weakly_connected_components()
+{
+    // 2 can't reach 0 along edges, but they are weakly connected
+    let g = Graph::from([(0,1), (2,1), (3,4), (5,4), (6,6)]);
+
+    let components = Connectivity::weakly_connected_components(&g);
+    assert_eq!(3, components.count());
+    let members = components.members();
+    assert_eq!(vec![0, 1, 2], values(&g, &members[0]));
+    assert_eq!(vec![3, 4, 5], values(&g, &members[1]));
+    assert_eq!(vec![6], values(&g, &members[2]));
+}
+
+#[test]
+#[should_panic]
+fn connected_components_of_directed_graph()
+{
+    let g = Graph::from([(0,1)]);
+    Connectivity::connected_components(&g);
+}
+
+#[test]
+fn weakly_connected_components_of_weighted_graph()
+{
+    let g = WeightedGraph::from([(0,1,1.0), (2,1,2.0), (3,4,0.5)]);
+    assert_eq!(2, Connectivity::weakly_connected_components(&g).count());
+}
+
+#[test]
+fn extract_components()
+{
+    let g = Graph::from_vec_undirected(vec![(0,1), (1,2), (3,4), (4,3)]);
+    let components = Connectivity::connected_components(&g);
+    let graphs = Connectivity::extract_components(&g, &components);
+
+    assert_eq!(2, graphs.len());
+    assert!(graphs.iter().all(|graph| !graph.is_directed()));
+    assert_eq!("0[1]\n1[0,2]\n2[1]\n", graphs[0].to_string());
+    assert_eq!("3[4,4]\n4[3,3]\n", graphs[1].to_string());
+}
+
+#[test]
+fn extract_many_components()
+{
+    let g = Graph::from_vec_undirected((0..20000).map(|i| (2 * i, 2 * i + 1)).collect());
+    let components = Connectivity::connected_components(&g);
+    let graphs = Connectivity::extract_components(&g, &components);
+
+    assert_eq!(20000, graphs.len());
+    assert!(graphs.iter().all(|graph| graph.len() == 2 && graph.edge_count() == 1));
+    assert_eq!("8[9]\n9[8]\n", graphs[4].to_string());
+}
+
+#[test]
+fn extract_directed_components()
+{
+    let g = Graph::from([(0,1), (2,3), (1,0), (3,3)]);
+    let components = Connectivity::weakly_connected_components(&g);
+    let graphs = Connectivity::extract_components(&g, &components);
+
+    assert_eq!(2, graphs.len());
+    assert!(graphs.iter().all(|graph| graph.is_directed()));
+    assert_eq!("0[1]\n1[0]\n", graphs[0].to_string());
+    assert_eq!("2[3]\n3[3]\n", graphs[1].to_string());
+}
+
+#[test]
+fn extract_strongly_connected_components()
+{
+    let g = Graph::from([(0,1), (1,2), (2,1), (2,3), (0,3)]);
+    let components = Connectivity::strongly_connected_components(&g);
+    let graphs = Connectivity::extract_components(&g, &components);
+
+    assert_eq!(3, graphs.len());
+    let mut printed: Vec<String> = graphs.iter().map(|graph| graph.to_string()).collect();
+    printed.sort();
+    assert_eq!(vec!["0[]\n", "1[2]\n2[1]\n", "3[]\n"], printed);
+    assert_eq!(2, graphs.iter().map(|graph| graph.edge_count()).sum::<usize>());
+}
+
+#[test]
+fn drop_small_islands()
+{
+    let g = Graph::from([(0,1), (1,2), (2,3), (10,11), (20,20)]);
+    let components = Connectivity::weakly_connected_components(&g);
+
+    let kept = components.members()
+        .into_iter()
+        .filter(|members| members.len() >= 3)
+        .flatten()
+        .collect::<Vec<Node>>();
+    let cleaned = g.induced_subgraph(&kept);
+
+    assert!(cleaned.is_directed());
+    assert_eq!(4, cleaned.len());
+    assert_eq!(vec![(0,1), (1,2), (2,3)], cleaned.edges().map(|edge| cleaned.get_edge_values(edge)).collect::<Vec<_>>());
+}
+
+#[test]
+fn strongly_connected_components()
+{
+    let g = Graph::from([(0,1), (1,2), (2,0), (2,3), (3,4), (4,3), (5,5), (4,6)]);
+    let components = Connectivity::strongly_connected_components(&g);
+
+    assert_eq!(4, components.count());
+    let members = components.members();
+    assert_eq!(vec![0, 1, 2], values(&g, &members[0]));
+    assert_eq!(vec![3, 4], values(&g, &members[1]));
+    assert_eq!(vec![5], values(&g, &members[2]));
+    assert_eq!(vec![6], values(&g, &members[3]));
+}
+
+#[test]
+fn strongly_connected_components_of_long_path()
+{
+    // Deep enough to overflow the stack of a recursive implementation
+    let length = 200_000;
+    let mut g = WeightedGraph::new_directed();
+    let first = g.add_node(0);
+    let mut previous = first;
+    for value in 1..length {
+        let node = g.add_node(value);
+        g.add_edge(previous, node, ());
+        previous = node;
+    }
+    assert_eq!(length, Connectivity::strongly_connected_components(&g).count());
+
+    g.add_edge(previous, first, ());
+    assert_eq!(1, Connectivity::strongly_connected_components(&g).count());
+}
+
+#[test]
+fn condensation()
+{
+    let g = Graph::from([(0,1), (1,0), (1,2), (0,2), (2,3), (3,2), (3,4), (0,4)]);
+    let dag = Connectivity::condensation(&g);
+    let components = Connectivity::strongly_connected_components(&g);
+
+    assert_eq!(3, dag.len());
+    let node = |value| g.find_node_with_value(&value).unwrap();
+    for value in 0..5 {
+        let members = &dag[dag.nodes().nth(components[node(value)]).unwrap().0];
+        assert!(members.contains(&node(value)));
+    }
+
+    // Parallel edges between components are merged
+    let edges = dag.edges().map(|edge| (dag[edge.source].len(), dag[edge.target].len())).collect::<Vec<_>>();
+    assert_eq!(vec![(2, 2), (2, 1), (2, 1)], edges);
+}
+
+fn edge_values<T: Copy + Ord>(graph: &Graph<T>, edges: &[grapher::graph::Edge]) -> Vec<(T, T)> {
+    let mut values = edges.iter()
+        .map(|edge| {
+            let (a, b) = graph.get_edge_values(*edge);
+            (a.min(b), a.max(b))
+        })
+        .collect::<Vec<_>>();
+    values.sort();
+    values
+}
+
+// Two triangles joined by the 2-3 edge, with 5-6 hanging off the second one
+fn bowtie() -> Graph<usize> {
+    Graph::from_vec_undirected(vec![(0,1), (1,2), (2,0), (2,3), (3,4), (4,5), (5,3), (5,6)])
+}
+
+#[test]
+fn bridges()
+{
+    let g = bowtie();
+    assert_eq!(vec![(2,3), (5,6)], edge_values(&g, &Connectivity::bridges(&g)));
+}
+
+#[test]
+fn parallel_edges_are_not_bridges()
+{
+    let g = Graph::from_vec_undirected(vec![(0,1), (0,1), (1,2), (2,2)]);
+    assert_eq!(vec![(1,2)], edge_values(&g, &Connectivity::bridges(&g)));
+}
+
+#[test]
+fn articulation_points()
+{
+    let g = bowtie();
+    let points = Connectivity::articulation_points(&g);
+    assert_eq!(vec![2, 3, 5], points.iter().map(|node| g[*node]).collect::<Vec<_>>());
+
+    // Root of the DFS with two children
+    let star = Graph::from_vec_undirected(vec![(0,1), (0,2), (0,3)]);
+    assert_eq!(vec![0], Connectivity::articulation_points(&star).iter().map(|node| star[*node]).collect::<Vec<_>>());
+}
+
+#[test]
+fn biconnected_components()
+{
+    let g = bowtie();
+    let mut components = Connectivity::biconnected_components(&g)
+        .iter()
+        .map(|component| edge_values(&g, component))
+        .collect::<Vec<_>>();
+    components.sort();
+
+    assert_eq!(vec![
+        vec![(0,1), (0,2), (1,2)],
+        vec![(2,3)],
+        vec![(3,4), (3,5), (4,5)],
+        vec![(5,6)]], components);
+}
+
+#[test]
+fn biconnected_components_with_parallel_edges()
+{
+    let g = Graph::from_vec_undirected(vec![(0,1), (1,0), (1,2)]);
+    let mut components = Connectivity::biconnected_components(&g)
+        .iter()
+        .map(|component| edge_values(&g, component))
+        .collect::<Vec<_>>();
+    components.sort();
+
+    assert_eq!(vec![vec![(0,1), (0,1)], vec![(1,2)]], components);
+}
+
+#[test]
+#[should_panic]
+fn bridges_of_directed_graph()
+{
+    let g = Graph::from([(0,1)]);
+    Connectivity::bridges(&g);
+}
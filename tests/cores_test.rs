@@ -0,0 +1,95 @@
+use std::fs::File;
+use std::io::BufReader;
+use std::io::prelude::*;
+
+use grapher::cores::Cores;
+use grapher::graph::Graph;
+use grapher::path_finder::NodeIndexable;
+use grapher::weighted_graph::{Node, WeightedGraph};
+
+// Checks that every node has at most `degeneracy` neighbours later in the ordering
+fn assert_degeneracy_ordering<T>(graph: &Graph<T>, ordering: &[Node], degeneracy: usize) {
+    let mut position = vec![usize::MAX; graph.node_count()];
+    for (index, node) in ordering.iter().enumerate() {
+        position[graph.index_of(*node)] = index;
+    }
+    assert!(position.iter().all(|&index| index != usize::MAX));
+
+    for (node, _) in graph.nodes() {
+        let mut later: Vec<usize> = graph.get_neighbours(node)
+            .map(|edge| graph.index_of(edge.target))
+            .filter(|&neighbour| position[neighbour] > position[graph.index_of(node)])
+            .collect();
+        later.sort_unstable();
+        later.dedup();
+        assert!(later.len() <= degeneracy);
+    }
+}
+
+#[test]
+fn clique_with_tail()
+{
+    let g = Graph::from_vec_undirected(vec![(0,1), (0,2), (0,3), (1,2), (1,3), (2,3), (3,4), (4,5), (5,5), (4,5)]);
+    let cores = Cores::core_numbers(&g);
+
+    assert_eq!(3, cores.degeneracy);
+    assert_eq!(&[3, 3, 3, 3, 1, 1], cores.core.values());
+    assert_degeneracy_ordering(&g, &cores.ordering, cores.degeneracy);
+
+    let clique = Cores::k_core(&g, &cores, 2);
+    assert_eq!(4, clique.len());
+    assert!(clique.nodes().all(|(_, value)| *value < 4));
+    assert_eq!(6, clique.edges().count() / 2);
+    assert_eq!(6, Cores::k_core(&g, &cores, 0).len());
+    assert_eq!(0, Cores::k_core(&g, &cores, 4).len());
+}
+
+#[test]
+fn weighted_graph_k_core()
+{
+    let g = WeightedGraph::from_vec_undirected(vec![(0,1,5), (1,2,5), (2,0,5), (2,3,1)]);
+    let cores = Cores::core_numbers(&g);
+
+    assert_eq!(2, cores.degeneracy);
+    let core = g.induced_subgraph(&cores.k_core(2));
+    assert_eq!(3, core.len());
+}
+
+#[test]
+fn empty_graph()
+{
+    let g: Graph<usize> = Graph::new_undirected();
+    let cores = Cores::core_numbers(&g);
+    assert_eq!(0, cores.degeneracy);
+    assert!(cores.ordering.is_empty());
+}
+
+#[test]
+#[should_panic]
+fn directed_graph()
+{
+    Cores::core_numbers(&Graph::from([(0,1), (1,0)]));
+}
+
+#[test]
+fn facebook_cores()
+{
+    let file = File::open("tests/data/facebook_test_data.txt").expect("Can't open data");
+    let edges: Vec<(usize, usize)> = BufReader::new(file).lines()
+        .map(|line| {
+            let nodes: Vec<usize> = line.expect("Can't read line")
+                .split_whitespace()
+                .map(|n| n.parse().expect("Can't parse node"))
+                .collect();
+            (nodes[0], nodes[1])
+        })
+        .collect();
+    let g = Graph::from_vec_undirected(edges);
+    let cores = Cores::core_numbers(&g);
+
+    assert_eq!(53, cores.degeneracy);
+    assert_eq!(336, cores.k_core(53).len());
+    assert_eq!(2329, cores.core.values().iter().filter(|&&core| core == 1).count());
+    assert_eq!(21, cores.core[g.find_node_with_value(&30).unwrap()]);
+    assert_degeneracy_ordering(&g, &cores.ordering, cores.degeneracy);
+}
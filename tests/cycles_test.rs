@@ -0,0 +1,135 @@
+use std::collections::BTreeSet;
+
+use grapher::cycles::Cycles;
+use grapher::graph::Graph;
+use grapher::weighted_graph::{Edge, Node, WeightedGraph};
+
+// Values along the cycle, rotated to start at the lowest one
+fn normalized<T: Copy + Ord>(graph: &Graph<T>, cycle: &[Node]) -> Vec<T> {
+    let values: Vec<T> = cycle.iter().map(|node| graph[*node]).collect();
+    let start = (0..values.len()).min_by_key(|&position| values[position]).unwrap();
+    values[start..].iter().chain(values[..start].iter()).copied().collect()
+}
+
+fn assert_closed(cycle: &[Edge]) {
+    for (position, edge) in cycle.iter().enumerate() {
+        assert_eq!(edge.target, cycle[(position + 1) % cycle.len()].source);
+    }
+}
+
+#[test]
+fn find_directed_cycle()
+{
+    let g = Graph::from([(0,1), (1,2), (2,3), (3,1), (0,4)]);
+    let cycle = Cycles::find_cycle(&g).unwrap();
+
+    assert_eq!(3, cycle.len());
+    assert_closed(&cycle);
+    assert!(Cycles::has_cycle(&g));
+    assert!(!Cycles::has_cycle(&Graph::from([(0,1), (1,2), (0,2)])));
+}
+
+#[test]
+fn find_undirected_cycle()
+{
+    let tree = Graph::from_vec_undirected(vec![(0,1), (1,2), (1,3), (3,4)]);
+    assert!(!Cycles::has_cycle(&tree));
+
+    let g = Graph::from_vec_undirected(vec![(0,1), (1,2), (1,3), (3,4), (4,1)]);
+    let cycle = Cycles::find_cycle(&g).unwrap();
+    assert_eq!(3, cycle.len());
+    assert_closed(&cycle);
+
+    let parallel = WeightedGraph::from_vec_undirected(vec![(0,1,1), (1,0,2)]);
+    let cycle = Cycles::find_cycle(&parallel).unwrap();
+    assert_eq!(2, cycle.len());
+    assert_closed(&cycle);
+}
+
+#[test]
+fn self_loops_are_cycles()
+{
+    let g = Graph::from([(0,0)]);
+    let cycle = Cycles::find_cycle(&g).unwrap();
+    assert_eq!(1, cycle.len());
+    assert_eq!(cycle[0].source, cycle[0].target);
+
+    let undirected = Graph::from_vec_undirected(vec![(0,1), (1,1)]);
+    assert_eq!(1, Cycles::find_cycle(&undirected).unwrap().len());
+    assert_eq!(vec![vec![1]], Cycles::cycle_basis(&undirected).iter().map(|cycle| normalized(&undirected, cycle)).collect::<Vec<_>>());
+
+    let cycles: Vec<_> = Cycles::simple_cycles(&g, None).map(|cycle| normalized(&g, &cycle)).collect();
+    assert_eq!(vec![vec![0]], cycles);
+}
+
+#[test]
+fn cycle_basis()
+{
+    // Two squares sharing an edge, with a tail and a separate triangle
+    let g = Graph::from_vec_undirected(vec![(0,1), (1,2), (2,3), (3,0), (1,4), (4,5), (5,2), (5,6), (7,8), (8,9), (9,7)]);
+    let basis = Cycles::cycle_basis(&g);
+
+    assert_eq!(3, basis.len());
+    for cycle in basis.iter() {
+        let values = normalized(&g, cycle);
+        for (position, value) in values.iter().enumerate() {
+            let next = values[(position + 1) % values.len()];
+            assert!(g.edges().any(|edge| g.get_edge_values(edge) == (*value, next)));
+        }
+    }
+    let lengths: Vec<usize> = basis.iter().map(Vec::len).collect();
+    assert_eq!(vec![4, 4, 3], lengths);
+}
+
+#[test]
+fn simple_cycles()
+{
+    let g = Graph::from([(0,1), (1,2), (2,0), (0,2), (2,1), (1,0), (2,3), (3,3)]);
+    let cycles: BTreeSet<Vec<usize>> = Cycles::simple_cycles(&g, None).map(|cycle| normalized(&g, &cycle)).collect();
+
+    let expected = BTreeSet::from([vec![0, 1], vec![1, 2], vec![0, 2], vec![0, 1, 2], vec![0, 2, 1], vec![3]]);
+    assert_eq!(expected, cycles);
+}
+
+#[test]
+fn simple_cycles_with_length_limit()
+{
+    let g = Graph::from([(0,1), (1,2), (2,0), (0,2), (2,1), (1,0), (2,3), (3,3)]);
+    let cycles: BTreeSet<Vec<usize>> = Cycles::simple_cycles(&g, Some(2)).map(|cycle| normalized(&g, &cycle)).collect();
+
+    assert_eq!(BTreeSet::from([vec![0, 1], vec![1, 2], vec![0, 2], vec![3]]), cycles);
+    assert_eq!(0, Cycles::simple_cycles(&g, Some(0)).count());
+}
+
+#[test]
+fn simple_cycles_are_listed_lazily()
+{
+    // Complete directed graph of 12 nodes has more than 10^8 cycles
+    let mut edges = vec![];
+    for i in 0..12 {
+        for j in 0..12 {
+            if i != j {
+                edges.push((i, j));
+            }
+        }
+    }
+    let g = Graph::from_vec_directed(edges);
+
+    let first: Vec<_> = Cycles::simple_cycles(&g, None).take(1000).collect();
+    assert_eq!(1000, first.len());
+    assert_eq!(66, Cycles::simple_cycles(&g, Some(2)).count());
+}
+
+#[test]
+fn acyclic_graph_has_no_simple_cycles()
+{
+    let g = Graph::from([(0,1), (1,2), (0,2), (2,3)]);
+    assert_eq!(0, Cycles::simple_cycles(&g, None).count());
+}
+
+#[test]
+#[should_panic]
+fn simple_cycles_of_undirected_graph()
+{
+    Cycles::simple_cycles(&Graph::from_vec_undirected(vec![(0,1)]), None);
+}
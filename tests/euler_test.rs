@@ -0,0 +1,88 @@
+use std::collections::HashSet;
+
+use grapher::euler::Euler;
+use grapher::graph::{Graph, Edge};
+use grapher::weighted_graph::WeightedGraph;
+
+// Checks that the trail follows edges one after another and uses `edge_count` different edges
+fn assert_trail(trail: &[Edge], edge_count: usize, closed: bool) {
+    assert_eq!(edge_count, trail.len());
+    assert_eq!(edge_count, trail.iter().collect::<HashSet<_>>().len());
+    for pair in trail.windows(2) {
+        assert_eq!(pair[0].target, pair[1].source);
+    }
+    if closed {
+        assert_eq!(trail.last().unwrap().target, trail[0].source);
+    }
+}
+
+#[test]
+fn directed_circuit()
+{
+    let g = Graph::from([(0,1), (1,2), (2,0), (0,3), (3,4), (4,0), (4,4)]);
+    let circuit = Euler::circuit(&g).unwrap();
+
+    assert_trail(&circuit, 7, true);
+    assert!(Euler::has_circuit(&g));
+    assert!(Euler::has_path(&g));
+}
+
+#[test]
+fn directed_path()
+{
+    let g = Graph::from([(0,1), (1,2), (2,0), (0,3)]);
+    assert!(!Euler::has_circuit(&g));
+
+    let path = Euler::path(&g).unwrap();
+    assert_trail(&path, 4, false);
+    assert_eq!((0, 3), g.get_edge_values(*path.last().unwrap()));
+    assert_eq!(0, g.get_edge_values(path[0]).0);
+}
+
+#[test]
+fn undirected_path()
+{
+    // The house drawn in one stroke
+    let g = Graph::from_vec_undirected(vec![(0,1), (1,2), (2,3), (3,0), (0,2), (1,3), (2,4), (3,4)]);
+    assert!(!Euler::has_circuit(&g));
+
+    let path = Euler::path(&g).unwrap();
+    assert_trail(&path, 8, false);
+    let ends = [g.get_edge_values(path[0]).0, g.get_edge_values(*path.last().unwrap()).1];
+    assert!(ends == [0, 1] || ends == [1, 0]);
+}
+
+#[test]
+fn parallel_edges_are_used_once_each()
+{
+    let g = Graph::from([(0,1), (0,1), (1,0), (1,0)]);
+    assert_trail(&Euler::circuit(&g).unwrap(), 4, true);
+
+    let undirected = WeightedGraph::from_vec_undirected(vec![(0,1,1), (0,1,2), (1,2,3), (2,2,4)]);
+    assert!(!Euler::has_circuit(&undirected));
+    assert_trail(&Euler::path(&undirected).unwrap(), 4, false);
+}
+
+#[test]
+fn no_eulerian_trail()
+{
+    // Königsberg bridges
+    let konigsberg = Graph::from_vec_undirected(vec![(0,1), (0,1), (0,2), (0,2), (0,3), (1,3), (2,3)]);
+    assert!(!Euler::has_path(&konigsberg));
+
+    let unbalanced = Graph::from([(0,1), (0,2), (0,3)]);
+    assert!(!Euler::has_path(&unbalanced));
+
+    // Two separate cycles
+    let disconnected = Graph::from([(0,1), (1,0), (2,3), (3,2)]);
+    assert!(!Euler::has_path(&disconnected));
+}
+
+#[test]
+fn graph_without_edges()
+{
+    let mut g = Graph::new_undirected();
+    g.add_node(0);
+    assert_eq!(Some(vec![]), Euler::circuit(&g));
+    assert_eq!(Some(vec![]), Euler::path(&g));
+}
@@ -0,0 +1,202 @@
+use grapher::flow::{MaxFlow, MinCostFlow, FlowResult, CostFlowResult};
+use grapher::graph::Graph;
+use grapher::weighted_graph::{WeightedGraph, Node};
+
+fn node<T: PartialEq, W>(graph: &WeightedGraph<T, W>, value: T) -> Node {
+    graph.find_node_with_value(&value).unwrap()
+}
+
+fn assert_is_valid_flow<T>(graph: &WeightedGraph<T, i32>, source: Node, target: Node, result: &FlowResult<i32>) {
+    let mut balance = vec![0; graph.len()];
+    for (edge, capacity) in graph.edges() {
+        let flow = result.flow[edge];
+        assert!(0 <= flow && flow <= *capacity);
+        balance[graph.nodes().position(|(node, _)| node == edge.source).unwrap()] -= flow;
+        balance[graph.nodes().position(|(node, _)| node == edge.target).unwrap()] += flow;
+    }
+    for (index, (node, _)) in graph.nodes().enumerate() {
+        let expected = if node == source { -result.value } else if node == target { result.value } else { 0 };
+        assert_eq!(expected, balance[index]);
+    }
+
+    // Max-flow min-cut theorem
+    let cut_capacity: i32 = result.cut_edges.iter().map(|edge| *graph.get_weight(*edge)).sum();
+    assert_eq!(result.value, cut_capacity);
+    assert!(result.source_side[source]);
+    assert!(!result.source_side[target]);
+}
+
+fn network() -> WeightedGraph<&'static str, i32> {
+    WeightedGraph::from([
+        ("s","v1",16), ("s","v2",13), ("v2","v1",4), ("v1","v3",12), ("v3","v2",9),
+        ("v2","v4",14), ("v4","v3",7), ("v3","t",20), ("v4","t",4)])
+}
+
+#[test]
+fn max_flow()
+{
+    let g = network();
+    let (source, target) = (node(&g, "s"), node(&g, "t"));
+    let result = MaxFlow::dinic(&g, source, target);
+
+    assert_eq!(23, result.value);
+    assert_is_valid_flow(&g, source, target, &result);
+}
+
+#[test]
+fn min_cut()
+{
+    let g = network();
+    let result = MaxFlow::dinic(&g, node(&g, "s"), node(&g, "t"));
+
+    let mut source_side = g.nodes().filter(|(node, _)| result.source_side[*node]).map(|(_, value)| *value).collect::<Vec<_>>();
+    source_side.sort();
+    assert_eq!(vec!["s", "v1", "v2", "v4"], source_side);
+
+    let mut cut = result.cut_edges.iter().map(|edge| g.get_edge_values(*edge)).collect::<Vec<_>>();
+    cut.sort();
+    assert_eq!(vec![("v1","v3"), ("v4","t"), ("v4","v3")], cut);
+}
+
+#[test]
+fn edge_disjoint_paths_of_unweighted_graph()
+{
+    // Every edge of a Graph has capacity 1
+    let g = Graph::from([(0,1), (0,2), (0,3), (1,4), (2,4), (3,2), (4,5), (2,5), (1,5)]);
+    let (source, target) = (g.find_node_with_value(&0).unwrap(), g.find_node_with_value(&5).unwrap());
+    let result = MaxFlow::dinic(&g, source, target);
+
+    assert_eq!(3, result.value);
+    assert_eq!(3, result.cut_edges.len());
+}
+
+#[test]
+fn no_path_between_nodes()
+{
+    let g = WeightedGraph::from([(0,1,5), (2,3,5), (1,1,2)]);
+    let (source, target) = (node(&g, 0), node(&g, 3));
+    let result = MaxFlow::dinic(&g, source, target);
+
+    assert_eq!(0, result.value);
+    assert!(result.cut_edges.is_empty());
+    assert_eq!(&[0, 0, 0], result.flow.values());
+    assert_is_valid_flow(&g, source, target, &result);
+}
+
+#[test]
+fn parallel_and_zero_capacity_edges()
+{
+    let g = WeightedGraph::from([(0,1,3), (0,1,2), (1,2,4), (0,2,0), (2,1,1)]);
+    let (source, target) = (node(&g, 0), node(&g, 2));
+    let result = MaxFlow::dinic(&g, source, target);
+
+    assert_eq!(4, result.value);
+    assert_is_valid_flow(&g, source, target, &result);
+}
+
+#[test]
+fn max_flow_of_long_path()
+{
+    // Deep enough to overflow the stack of a recursive implementation
+    let length = 200_000;
+    let mut g = WeightedGraph::new_directed();
+    let first = g.add_node(0);
+    let mut previous = first;
+    for value in 1..length {
+        let node = g.add_node(value);
+        g.add_edge(previous, node, 1 + (value % 7) as u64);
+        previous = node;
+    }
+    assert_eq!(1, MaxFlow::dinic(&g, first, previous).value);
+}
+
+#[test]
+#[should_panic]
+fn max_flow_of_undirected_graph()
+{
+    let g = WeightedGraph::from_vec_undirected(vec![(0,1,1)]);
+    MaxFlow::dinic(&g, node(&g, 0), node(&g, 1));
+}
+
+fn assert_is_valid_cost_flow<T>(graph: &WeightedGraph<T, (i32, i32)>, source: Node, target: Node, result: &CostFlowResult<i32>) {
+    let mut balance = vec![0; graph.len()];
+    let mut cost = 0;
+    for (edge, (capacity, edge_cost)) in graph.edges() {
+        let flow = result.flow[edge];
+        assert!(0 <= flow && flow <= *capacity);
+        cost += flow * edge_cost;
+        balance[graph.nodes().position(|(node, _)| node == edge.source).unwrap()] -= flow;
+        balance[graph.nodes().position(|(node, _)| node == edge.target).unwrap()] += flow;
+    }
+    for (index, (node, _)) in graph.nodes().enumerate() {
+        let expected = if node == source { -result.value } else if node == target { result.value } else { 0 };
+        assert_eq!(expected, balance[index]);
+    }
+    assert_eq!(result.cost, cost);
+}
+
+// Two routes from s to t: a cheap narrow one through a and an expensive wide one through b
+fn cost_network() -> WeightedGraph<char, (i32, i32)> {
+    WeightedGraph::from([
+        ('s','a',(2,1)), ('a','t',(2,1)), ('s','b',(5,3)), ('b','t',(5,3)), ('a','b',(1,1)), ('s','t',(1,10))])
+}
+
+#[test]
+fn min_cost_max_flow()
+{
+    let g = cost_network();
+    let (source, target) = (node(&g, 's'), node(&g, 't'));
+    let result = MinCostFlow::min_cost_max_flow(&g, source, target);
+
+    assert_eq!(8, result.value);
+    assert_eq!(2 * 2 + 5 * 6 + 10, result.cost);
+    assert_is_valid_cost_flow(&g, source, target, &result);
+
+    // Same value as the plain maximum flow
+    let mut capacities = WeightedGraph::new_directed();
+    for (_, value) in g.nodes() {
+        capacities.add_node(*value);
+    }
+    for (edge, (capacity, _)) in g.edges() {
+        capacities.add_edge(edge.source, edge.target, *capacity);
+    }
+    assert_eq!(MaxFlow::dinic(&capacities, source, target).value, result.value);
+}
+
+#[test]
+fn min_cost_flow_of_given_amount()
+{
+    let g = cost_network();
+    let (source, target) = (node(&g, 's'), node(&g, 't'));
+
+    let cheapest = |amount| MinCostFlow::min_cost_flow(&g, source, target, amount).map(|result| {
+        assert_is_valid_cost_flow(&g, source, target, &result);
+        result.cost
+    });
+    assert_eq!(Some(0), cheapest(0));
+    assert_eq!(Some(4), cheapest(2));
+    // s-a is full, so the third unit takes s-b-t
+    assert_eq!(Some(10), cheapest(3));
+    assert_eq!(Some(44), cheapest(8));
+    assert_eq!(None, cheapest(9));
+}
+
+#[test]
+fn min_cost_flow_with_negative_costs()
+{
+    let g = WeightedGraph::from([(0,1,(1,-5)), (1,3,(1,2)), (0,2,(2,1)), (2,3,(2,1)), (2,1,(1,-1))]);
+    let (source, target) = (node(&g, 0), node(&g, 3));
+    let result = MinCostFlow::min_cost_max_flow(&g, source, target);
+
+    assert_eq!(3, result.value);
+    assert_eq!(-3 + 2 + 2, result.cost);
+    assert_is_valid_cost_flow(&g, source, target, &result);
+}
+
+#[test]
+#[should_panic]
+fn min_cost_flow_with_negative_cycle()
+{
+    let g = WeightedGraph::from([(0,1,(1,1)), (1,2,(1,-2)), (2,1,(1,-2)), (2,3,(1,1))]);
+    MinCostFlow::min_cost_max_flow(&g, node(&g, 0), node(&g, 3));
+}
@@ -0,0 +1,242 @@
+use grapher::graph::{Graph, Node};
+use grapher::matching::{Matching, MatchingResult, OddCycleError};
+use grapher::weighted_graph::WeightedGraph;
+
+fn values<T: Copy>(graph: &Graph<T>, nodes: &[Node]) -> Vec<T> {
+    nodes.iter().map(|node| graph[*node]).collect()
+}
+
+fn assert_is_matching<T, W>(graph: &WeightedGraph<T, W>, matching: &MatchingResult) {
+    let mut matched = vec![false; graph.len()];
+    for edge in matching.edges.iter() {
+        for node in [edge.source, edge.target] {
+            let index = graph.nodes().position(|(other, _)| other == node).unwrap();
+            assert!(!matched[index]);
+            matched[index] = true;
+        }
+        assert_eq!(Some(edge.target), matching.mate[edge.source]);
+        assert_eq!(Some(edge.source), matching.mate[edge.target]);
+    }
+    assert_eq!(2 * matching.edges.len(), matching.mate.values().iter().filter(|mate| mate.is_some()).count());
+}
+
+#[test]
+fn bipartition()
+{
+    let g = Graph::from_vec_undirected(vec![(0,1), (1,2), (2,3), (3,0), (4,5)]);
+    let bipartition = Matching::bipartition(&g).unwrap();
+
+    assert_eq!(vec![0, 2, 4], values(&g, &bipartition.left));
+    assert_eq!(vec![1, 3, 5], values(&g, &bipartition.right));
+    for edge in g.edges() {
+        assert_ne!(bipartition.is_left[edge.source], bipartition.is_left[edge.target]);
+    }
+}
+
+#[test]
+fn bipartition_reports_odd_cycle()
+{
+    let g = Graph::from_vec_undirected(vec![(0,1), (1,2), (2,3), (3,4), (4,0), (0,5)]);
+    let OddCycleError { cycle } = Matching::bipartition(&g).unwrap_err();
+
+    assert_eq!(5, cycle.len());
+    for (index, node) in cycle.iter().enumerate() {
+        let next = cycle[(index + 1) % cycle.len()];
+        assert!(g.get_neighbours(*node).any(|edge| edge.target == next));
+    }
+}
+
+#[test]
+fn bipartition_ignores_edge_directions()
+{
+    let g = Graph::from([(0,1), (2,1), (2,3)]);
+    assert_eq!(vec![0, 2], values(&g, &Matching::bipartition(&g).unwrap().left));
+
+    let error = Matching::bipartition(&Graph::from([(0,1), (1,2), (0,2)])).unwrap_err();
+    assert_eq!(3, error.cycle.len());
+    assert_eq!(format!("graph contains an odd cycle: {:?}", error.cycle), error.to_string());
+}
+
+#[test]
+fn self_loop_is_odd_cycle()
+{
+    let g = Graph::from_vec_undirected(vec![(0,1), (1,1)]);
+    assert_eq!(vec![1], values(&g, &Matching::bipartition(&g).unwrap_err().cycle));
+}
+
+#[test]
+fn hopcroft_karp()
+{
+    // Greedy matching a-1, b-2 blocks c, the maximum matching has 4 edges
+    let g = WeightedGraph::from_vec_undirected(vec![
+        ('a','1',()), ('a','2',()), ('b','2',()), ('b','3',()), ('c','1',()), ('d','3',()), ('d','4',()), ('e','4',())]);
+    let matching = Matching::hopcroft_karp(&g);
+
+    assert_eq!(4, matching.edges.len());
+    assert_is_matching(&g, &matching);
+}
+
+#[test]
+fn hopcroft_karp_of_complete_bipartite_graph()
+{
+    let mut g = WeightedGraph::new_directed();
+    let left = (0..30).map(|value| g.add_node(value)).collect::<Vec<_>>();
+    let right = (30..50).map(|value| g.add_node(value)).collect::<Vec<_>>();
+    for a in left.iter() {
+        for b in right.iter() {
+            g.add_edge(*b, *a, ());
+        }
+    }
+    let matching = Matching::hopcroft_karp(&g);
+
+    assert_eq!(20, matching.edges.len());
+    assert_is_matching(&g, &matching);
+}
+
+#[test]
+#[should_panic]
+fn hopcroft_karp_of_non_bipartite_graph()
+{
+    Matching::hopcroft_karp(&Graph::from_vec_undirected(vec![(0,1), (1,2), (2,0)]));
+}
+
+// Workers a, b, c and jobs x, y, z with the cost of every worker doing every job
+fn assignment(costs: &[[i32; 3]; 3]) -> WeightedGraph<char, i32> {
+    let mut edges = Vec::new();
+    for (worker, row) in ['a', 'b', 'c'].iter().zip(costs) {
+        for (job, cost) in ['x', 'y', 'z'].iter().zip(row) {
+            edges.push((*worker, *job, *cost));
+        }
+    }
+    WeightedGraph::from_vec_undirected(edges)
+}
+
+fn pairs(graph: &WeightedGraph<char, i32>, matching: &MatchingResult) -> Vec<(char, char)> {
+    let mut pairs = matching.edges.iter().map(|edge| graph.get_edge_values(*edge)).collect::<Vec<_>>();
+    pairs.sort();
+    pairs
+}
+
+#[test]
+fn hungarian()
+{
+    let g = assignment(&[[4, 1, 3], [2, 0, 5], [3, 2, 2]]);
+    let (matching, cost) = Matching::hungarian(&g).unwrap();
+
+    assert_eq!(5, cost);
+    assert_eq!(vec![('a','y'), ('b','x'), ('c','z')], pairs(&g, &matching));
+    assert_is_matching(&g, &matching);
+}
+
+#[test]
+fn hungarian_with_negative_weights()
+{
+    // Maximum profit is the minimum of negated profits
+    let g = assignment(&[[-7, -5, -1], [-2, -8, -3], [-4, -6, -9]]);
+    let (matching, cost) = Matching::hungarian(&g).unwrap();
+
+    assert_eq!(-24, cost);
+    assert_eq!(vec![('a','x'), ('b','y'), ('c','z')], pairs(&g, &matching));
+}
+
+#[test]
+fn hungarian_of_unbalanced_graph()
+{
+    // Only two workers, one job stays free
+    let g = WeightedGraph::from_vec_undirected(vec![
+        ('a','x',5), ('a','y',2), ('a','z',9), ('b','x',1), ('b','y',3), ('b','z',1), ('b','x',0)]);
+    let (matching, cost) = Matching::hungarian(&g).unwrap();
+
+    // Parallel edges count with their lower weight
+    assert_eq!(2, cost);
+    assert_eq!(vec![('a','y'), ('b','x')], pairs(&g, &matching));
+}
+
+#[test]
+fn hungarian_without_assignment()
+{
+    // Workers a and b can only do x
+    let g = WeightedGraph::from_vec_undirected(vec![('a','x',1), ('b','x',2), ('c','x',4), ('c','y',0), ('c','z',3)]);
+    assert!(Matching::hungarian(&g).is_none());
+}
+
+#[test]
+fn hungarian_of_components_with_swapped_sides()
+{
+    // The smaller side of the second component is on the right, whichever node comes first
+    for second in [('u','t',1), ('t','u',1)] {
+        let g = WeightedGraph::from_vec_undirected(vec![('p','q',1), ('p','r',1), ('p','s',1), second, ('t','v',1)]);
+        let (matching, cost) = Matching::hungarian(&g).unwrap();
+
+        assert_eq!(2, cost);
+        assert_eq!(2, matching.edges.len());
+        assert_is_matching(&g, &matching);
+    }
+}
+
+#[test]
+fn edmonds_needs_blossom()
+{
+    // Triangle 1-2-3 with tails, greedy matching 0-1, 2-3 must be rearranged through the blossom
+    let g = WeightedGraph::from_vec_undirected(vec![(0,1,()), (1,2,()), (2,3,()), (3,1,()), (3,4,()), (2,5,())]);
+    let matching = Matching::edmonds(&g);
+
+    assert_eq!(3, matching.edges.len());
+    assert_is_matching(&g, &matching);
+}
+
+#[test]
+fn edmonds_of_odd_cycles()
+{
+    // Two pentagons joined by an edge have a perfect matching
+    let g = Graph::from_vec_undirected(vec![
+        (0,1), (1,2), (2,3), (3,4), (4,0), (5,6), (6,7), (7,8), (8,9), (9,5), (0,5), (3,3)]);
+    let matching = Matching::edmonds(&g);
+
+    assert_eq!(5, matching.edges.len());
+    assert!(matching.mate.values().iter().all(|mate| mate.is_some()));
+}
+
+#[test]
+fn edmonds_agrees_with_hopcroft_karp()
+{
+    let g = Graph::from_vec_undirected(vec![(0,10), (0,11), (1,10), (2,11), (2,12), (3,12), (3,13), (4,13), (5,10)]);
+    assert_eq!(Matching::hopcroft_karp(&g).edges.len(), Matching::edmonds(&g).edges.len());
+}
+
+#[test]
+fn max_weight_matching()
+{
+    // Taking the heavy middle edge alone beats both outer edges
+    let g = WeightedGraph::from_vec_undirected(vec![('a','b',5), ('b','c',11), ('c','d',5)]);
+    let (matching, weight) = Matching::max_weight_matching(&g);
+    assert_eq!(11, weight);
+    assert_eq!(vec![('b','c')], matching.edges.iter().map(|edge| g.get_edge_values(*edge)).collect::<Vec<_>>());
+
+    let g = WeightedGraph::from_vec_undirected(vec![('a','b',6), ('b','c',11), ('c','d',6)]);
+    assert_eq!(12, Matching::max_weight_matching(&g).1);
+}
+
+#[test]
+fn max_weight_matching_with_blossoms()
+{
+    // The heaviest edges 2-3 and 4-5 form blossoms with their neighbours, but the best matching avoids them
+    let g = WeightedGraph::from_vec_undirected(vec![
+        (1,2,9), (1,3,9), (2,3,10), (2,4,8), (3,5,8), (4,5,10), (5,6,6), (4,7,3), (6,7,1)]);
+    let (matching, weight) = Matching::max_weight_matching(&g);
+
+    assert_eq!(23, weight);
+    let mut pairs = matching.edges.iter().map(|edge| g.get_edge_values(*edge)).map(|(a, b)| (a.min(b), a.max(b))).collect::<Vec<_>>();
+    pairs.sort();
+    assert_eq!(vec![(1,3), (2,4), (5,6)], pairs);
+    assert_is_matching(&g, &matching);
+}
+
+#[test]
+fn max_weight_matching_skips_negative_edges()
+{
+    let g = WeightedGraph::from_vec_undirected(vec![(0,1,-2), (1,2,-1), (2,2,5)]);
+    let (matching, weight) = Matching::max_weight_matching(&g);
+    assert_eq!(0, weight);
+    assert!(matching.edges.is_empty());
+}
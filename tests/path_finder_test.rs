@@ -1,5 +1,6 @@
 use grapher::graph::{Node, Graph};
-use grapher::path_finder::PathFinder;
+use grapher::path_finder::{PathFinder, PathConstraints, ParetoCost};
+use grapher::weighted_graph::WeightedGraph;
 
 fn compare_results<T: std::fmt::Debug + Eq, const N: usize>(graph: &Graph<T>, values: [T; N], nodes: Option<Vec<Node>>) {
     let nodes = nodes.unwrap();
@@ -106,4 +107,350 @@ fn find_all_paths_disconnected_graph()
 {
     let g = Graph::from([(0,1), (2,3)]);
     assert_eq!(Vec::<Vec::<Node>>::new(), PathFinder::find_all_paths(&g, n(&g, 0), n(&g, 3)));
-}
\ No newline at end of file
+}
+
+#[test]
+fn find_shortest_path_unweighted()
+{
+    let g = Graph::from([(0,1), (0,2), (2,3), (1,3), (1,4), (3,4)]);
+
+    compare_results(&g, [0, 1, 4],
+        PathFinder::find_shortest_path_unweighted(&g,
+            n(&g, 0),
+            n(&g, 4)));
+}
+
+#[test]
+fn find_shortest_path_unweighted_same_node()
+{
+    let mut g = Graph::from([(0,1), (1,2), (2,0)]);
+    let node0 = n(&g, 0);
+    compare_results(&g, [0, 1, 2, 0],
+        PathFinder::find_shortest_path_unweighted(&g, node0, node0));
+
+    g.add_edge(node0, node0);
+    compare_results(&g, [0, 0],
+        PathFinder::find_shortest_path_unweighted(&g, node0, node0));
+}
+
+#[test]
+fn find_shortest_path_unweighted_disconnected()
+{
+    let g = Graph::from([(0,1),(2,3)]);
+    assert_eq!(None, PathFinder::find_shortest_path_unweighted(&g, n(&g, 0), n(&g, 2)));
+}
+
+#[test]
+fn find_shortest_path_zero_one()
+{
+    // 0 -> 3 directly costs 1, going around through zero edges costs 0
+    let g = WeightedGraph::from([(0,3,1u32), (0,1,0), (1,2,0), (2,3,0), (3,4,1), (2,4,1)]);
+    let path = PathFinder::find_shortest_path_zero_one(&g,
+        g.find_node_with_value(&0).unwrap(),
+        g.find_node_with_value(&4).unwrap()).unwrap();
+
+    assert_eq!(vec![0, 1, 2, 4], path.iter().map(|node| g[*node]).collect::<Vec<_>>());
+}
+
+#[test]
+fn find_shortest_path_zero_one_same_node()
+{
+    let g = WeightedGraph::from([(0,1,1u32), (1,0,1), (0,2,0), (2,0,0)]);
+    let node0 = g.find_node_with_value(&0).unwrap();
+    let path = PathFinder::find_shortest_path_zero_one(&g, node0, node0).unwrap();
+
+    assert_eq!(vec![0, 2, 0], path.iter().map(|node| g[*node]).collect::<Vec<_>>());
+}
+
+#[test]
+#[should_panic]
+fn find_shortest_path_zero_one_rejects_other_weights()
+{
+    let g = WeightedGraph::from([(0,1,2u32)]);
+    PathFinder::find_shortest_path_zero_one(&g,
+        g.find_node_with_value(&0).unwrap(),
+        g.find_node_with_value(&1).unwrap());
+}
+
+#[test]
+fn find_shortest_path_zero_one_matches_unweighted_on_graph()
+{
+    let g = Graph::from([(0,1), (0,2), (2,3), (1,3), (1,4), (3,4), (4,5)]);
+    for source in g.nodes() {
+        for target in g.nodes() {
+            let bfs = PathFinder::find_shortest_path_unweighted(&g, source.0, target.0);
+            let zero_one = PathFinder::find_shortest_path_zero_one(&g, source.0, target.0);
+            assert_eq!(bfs.map(|path| path.len()), zero_one.map(|path| path.len()));
+        }
+    }
+}
+
+#[test]
+fn find_shortest_path_does_not_stop_at_first_reached_target()
+{
+    // The direct edge reaches the target first, but going through 1 is cheaper
+    let g = WeightedGraph::from([(0,2,10u32), (0,1,1), (1,2,1)]);
+    let path = PathFinder::find_shortest_path(&g,
+        g.find_node_with_value(&0).unwrap(),
+        g.find_node_with_value(&2).unwrap()).unwrap();
+
+    assert_eq!(vec![0, 1, 2], path.iter().map(|node| g[*node]).collect::<Vec<_>>());
+}
+
+#[test]
+fn find_shortest_path_indexed()
+{
+    let g = WeightedGraph::from([(0,2,10u32), (0,1,1), (1,2,1), (2,3,1), (1,3,5)]);
+    let path = PathFinder::find_shortest_path_indexed(&g,
+        g.find_node_with_value(&0).unwrap(),
+        g.find_node_with_value(&3).unwrap()).unwrap();
+
+    assert_eq!(vec![0, 1, 2, 3], path.iter().map(|node| g[*node]).collect::<Vec<_>>());
+}
+
+#[test]
+fn find_shortest_path_indexed_same_node()
+{
+    let mut g = Graph::from([(0,1)]);
+    let node0 = n(&g, 0);
+    assert_eq!(None, PathFinder::find_shortest_path_indexed(&g, node0, node0));
+
+    g.add_edge(node0, node0);
+    compare_results(&g, [0, 0],
+        PathFinder::find_shortest_path_indexed(&g, node0, node0));
+}
+
+#[test]
+fn find_shortest_path_indexed_matches_hash_maps()
+{
+    let g = WeightedGraph::from([(0,1,4u64), (0,2,1), (2,1,1), (1,3,1), (2,3,7), (3,0,2), (3,4,3)]);
+    for source in g.nodes() {
+        for target in g.nodes() {
+            assert_eq!(
+                PathFinder::find_shortest_path(&g, source.0, target.0),
+                PathFinder::find_shortest_path_indexed(&g, source.0, target.0));
+        }
+    }
+}
+
+// Edges are [time, toll]
+fn toll_graph() -> WeightedGraph<u32, [u32; 2]> {
+    WeightedGraph::from([
+        (0,1,[1,10]), (1,3,[1,10]),
+        (0,2,[2,0]), (2,3,[3,0]),
+        (0,4,[1,0]), (4,5,[1,0]), (5,6,[1,0]), (6,3,[1,0])])
+}
+
+fn values(g: &WeightedGraph<u32, [u32; 2]>, path: &[Node]) -> Vec<u32> {
+    path.iter().map(|node| g[*node]).collect()
+}
+
+#[test]
+fn find_constrained_path_without_constraints()
+{
+    let g = toll_graph();
+    let (path, cost) = PathFinder::find_constrained_path(&g,
+        g.find_node_with_value(&0).unwrap(),
+        g.find_node_with_value(&3).unwrap(),
+        &PathConstraints::new()).unwrap();
+
+    assert_eq!(vec![0, 1, 3], values(&g, &path));
+    assert_eq!([2, 20], cost);
+}
+
+#[test]
+fn find_constrained_path_max_cost()
+{
+    let g = toll_graph();
+    let mut constraints = PathConstraints::new();
+    constraints.max_costs[1] = Some(5);
+
+    let (path, cost) = PathFinder::find_constrained_path(&g,
+        g.find_node_with_value(&0).unwrap(),
+        g.find_node_with_value(&3).unwrap(),
+        &constraints).unwrap();
+
+    assert_eq!(vec![0, 4, 5, 6, 3], values(&g, &path));
+    assert_eq!([4, 0], cost);
+}
+
+#[test]
+fn find_constrained_path_max_hops()
+{
+    let g = toll_graph();
+    let mut constraints = PathConstraints::new();
+    constraints.max_costs[1] = Some(5);
+    constraints.max_hops = Some(3);
+
+    let (path, cost) = PathFinder::find_constrained_path(&g,
+        g.find_node_with_value(&0).unwrap(),
+        g.find_node_with_value(&3).unwrap(),
+        &constraints).unwrap();
+
+    assert_eq!(vec![0, 2, 3], values(&g, &path));
+    assert_eq!([5, 0], cost);
+}
+
+#[test]
+fn find_constrained_path_forbidden_nodes_and_edges()
+{
+    let g = toll_graph();
+    let node = |value| g.find_node_with_value(&value).unwrap();
+
+    let mut constraints = PathConstraints::new();
+    constraints.forbidden_nodes.insert(node(1));
+    constraints.forbidden_edges.insert((node(0), node(2)));
+
+    let (path, _) = PathFinder::find_constrained_path(&g, node(0), node(3), &constraints).unwrap();
+    assert_eq!(vec![0, 4, 5, 6, 3], values(&g, &path));
+}
+
+#[test]
+fn find_constrained_path_forbids_parallel_edges_together()
+{
+    let g = WeightedGraph::from([(0,1,[1u32]), (0,1,[2]), (0,2,[2]), (2,1,[2])]);
+    let node = |value| g.find_node_with_value(&value).unwrap();
+
+    let mut constraints = PathConstraints::new();
+    constraints.forbidden_edges.insert((node(0), node(1)));
+
+    let (path, cost) = PathFinder::find_constrained_path(&g, node(0), node(1), &constraints).unwrap();
+    assert_eq!(vec![node(0), node(2), node(1)], path);
+    assert_eq!([4], cost);
+}
+
+#[test]
+fn find_constrained_path_infeasible()
+{
+    let g = toll_graph();
+    let mut constraints = PathConstraints::new();
+    constraints.max_costs[1] = Some(5);
+    constraints.max_hops = Some(1);
+
+    assert_eq!(None, PathFinder::find_constrained_path(&g,
+        g.find_node_with_value(&0).unwrap(),
+        g.find_node_with_value(&3).unwrap(),
+        &constraints));
+}
+
+#[test]
+fn find_pareto_paths()
+{
+    // Edges are [time, cost]
+    let g = WeightedGraph::from([
+        (0,1,[1u32,5]), (1,3,[1,5]),
+        (0,2,[3,1]), (2,3,[3,1]),
+        (0,4,[2,3]), (4,3,[2,3]),
+        (0,5,[3,5]), (5,3,[3,5])]);
+    let node = |value| g.find_node_with_value(&value).unwrap();
+
+    let front = PathFinder::find_pareto_paths(&g, node(0), node(3));
+    let front = front.iter().map(|(path, cost)| (values(&g, path), *cost)).collect::<Vec<_>>();
+
+    assert_eq!(vec![
+        (vec![0, 1, 3], [2, 10]),
+        (vec![0, 4, 3], [4, 6]),
+        (vec![0, 2, 3], [6, 2])], front);
+}
+
+#[test]
+fn find_pareto_paths_disconnected()
+{
+    let g = WeightedGraph::from([(0,1,[1u32,1]), (2,3,[1,1])]);
+    let front = PathFinder::find_pareto_paths(&g,
+        g.find_node_with_value(&0).unwrap(),
+        g.find_node_with_value(&3).unwrap());
+    assert!(front.is_empty());
+}
+
+// Cost type defined outside of the crate, comparing time first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct Trip {
+    time: u32,
+    cost: u32,
+    emissions: u32
+}
+
+impl ParetoCost for Trip {
+    fn zero() -> Self {
+        Trip { time: 0, cost: 0, emissions: 0 }
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        Trip { time: self.time + other.time, cost: self.cost + other.cost, emissions: self.emissions + other.emissions }
+    }
+
+    fn dominates(&self, other: &Self) -> bool {
+        self.time <= other.time && self.cost <= other.cost && self.emissions <= other.emissions
+    }
+}
+
+#[test]
+fn find_pareto_paths_own_cost_type()
+{
+    let trip = |time, cost, emissions| Trip { time, cost, emissions };
+    let g = WeightedGraph::from([
+        ("home", "work", trip(30, 5, 10)),
+        ("home", "station", trip(10, 2, 1)), ("station", "work", trip(25, 2, 1)),
+        ("home", "bridge", trip(20, 4, 6)), ("bridge", "work", trip(20, 4, 6))]);
+
+    let front = PathFinder::find_pareto_paths(&g,
+        g.find_node_with_value(&"home").unwrap(),
+        g.find_node_with_value(&"work").unwrap());
+
+    assert_eq!(vec![trip(30, 5, 10), trip(35, 4, 2)], front.iter().map(|(_, cost)| *cost).collect::<Vec<_>>());
+}
+
+// Leaves at every full 10 minutes and takes 5
+fn every_ten_minutes(time: u32) -> u32 {
+    time.div_ceil(10) * 10 + 5
+}
+
+fn walk(time: u32) -> u32 {
+    time + 12
+}
+
+fn transit_graph() -> WeightedGraph<&'static str, fn(u32) -> u32> {
+    WeightedGraph::from([
+        ("home", "stop", walk as fn(u32) -> u32),
+        ("stop", "work", every_ten_minutes),
+        ("home", "work", |time| time + 22)])
+}
+
+#[test]
+fn find_earliest_arrival()
+{
+    let g = transit_graph();
+    let node = |value| g.find_node_with_value(&value).unwrap();
+
+    // Walking the whole way arrives at 22, the bus leaves the stop at 20 and arrives at 25
+    let (path, arrival) = PathFinder::find_earliest_arrival(&g, node("home"), node("work"), 0).unwrap();
+    assert_eq!(vec!["home", "work"], path.iter().map(|node| g[*node]).collect::<Vec<_>>());
+    assert_eq!(22, arrival);
+
+    // Walking the whole way arrives at 30, the same bus still arrives at 25
+    let (path, arrival) = PathFinder::find_earliest_arrival(&g, node("home"), node("work"), 8).unwrap();
+    assert_eq!(vec!["home", "stop", "work"], path.iter().map(|node| g[*node]).collect::<Vec<_>>());
+    assert_eq!(25, arrival);
+}
+
+#[test]
+fn find_earliest_arrival_disconnected()
+{
+    let g = transit_graph();
+    let node = |value| g.find_node_with_value(&value).unwrap();
+    assert_eq!(None, PathFinder::find_earliest_arrival(&g, node("work"), node("home"), 0));
+}
+
+#[test]
+fn find_arrival_profile()
+{
+    let g = transit_graph();
+    let node = |value| g.find_node_with_value(&value).unwrap();
+
+    let profile = PathFinder::find_arrival_profile(&g, node("home"), node("work"), 0, 10, 1);
+    let profile = profile.iter().map(|(departure, arrival, path)| (*departure, *arrival, path.len())).collect::<Vec<_>>();
+
+    // Departing at 3..=8 all catch the same bus, so only 8 is worth it
+    assert_eq!(vec![(0, 22, 2), (1, 23, 2), (2, 24, 2), (8, 25, 3), (9, 31, 2), (10, 32, 2)], profile);
+}
@@ -0,0 +1,126 @@
+use grapher::graph::Graph;
+use grapher::spanning_tree::{SpanningTree, SpanningForest};
+use grapher::union_find::UnionFind;
+use grapher::weighted_graph::{WeightedGraph, Edge};
+
+fn edge_values(graph: &WeightedGraph<char, u32>, forest: &SpanningForest<u32>) -> Vec<(char, char, u32)> {
+    let mut values = forest.edges.iter()
+        .map(|edge| {
+            let (a, b) = graph.get_edge_values(*edge);
+            (a.min(b), a.max(b), *graph.get_weight(*edge))
+        })
+        .collect::<Vec<_>>();
+    values.sort();
+    values
+}
+
+// Classic example with a unique minimum spanning tree of weight 39
+fn example() -> WeightedGraph<char, u32> {
+    WeightedGraph::from_vec_undirected(vec![
+        ('a','b',7), ('a','d',5), ('b','c',8), ('b','d',9), ('b','e',7), ('c','e',5),
+        ('d','e',15), ('d','f',6), ('e','f',8), ('e','g',9), ('f','g',11)])
+}
+
+#[test]
+fn kruskal()
+{
+    let g = example();
+    let forest = SpanningTree::kruskal(&g);
+    assert_eq!(39, forest.total_weight);
+    assert_eq!(vec![('a','b',7), ('a','d',5), ('b','e',7), ('c','e',5), ('d','f',6), ('e','g',9)], edge_values(&g, &forest));
+}
+
+#[test]
+fn all_algorithms_agree()
+{
+    let g = example();
+    let kruskal = SpanningTree::kruskal(&g);
+    let prim = SpanningTree::prim(&g);
+    let boruvka = SpanningTree::boruvka(&g);
+
+    assert_eq!(kruskal.total_weight, prim.total_weight);
+    assert_eq!(edge_values(&g, &kruskal), edge_values(&g, &prim));
+    assert_eq!(edge_values(&g, &kruskal), edge_values(&g, &boruvka));
+}
+
+#[test]
+fn equal_weights_give_same_forest()
+{
+    // Every spanning tree of a square with a diagonal weighs 3
+    let g = WeightedGraph::from_vec_undirected(vec![('a','b',1), ('b','c',1), ('c','d',1), ('d','a',1), ('a','c',1)]);
+    let kruskal = SpanningTree::kruskal(&g);
+    let boruvka = SpanningTree::boruvka(&g);
+
+    assert_eq!(3, SpanningTree::prim(&g).total_weight);
+    let mut kruskal_edges = kruskal.edges.clone();
+    let mut boruvka_edges = boruvka.edges.clone();
+    let key = |edge: &Edge| g.get_edge_values(*edge);
+    kruskal_edges.sort_by_key(key);
+    boruvka_edges.sort_by_key(key);
+    assert_eq!(kruskal_edges, boruvka_edges);
+}
+
+#[test]
+fn spanning_forest_of_disconnected_graph()
+{
+    let mut g = WeightedGraph::from_vec_undirected(vec![('a','b',2), ('b','c',1), ('a','c',3), ('x','y',4), ('y','y',0)]);
+    g.add_node('z');
+
+    for forest in [SpanningTree::kruskal(&g), SpanningTree::prim(&g), SpanningTree::boruvka(&g)] {
+        assert_eq!(7, forest.total_weight);
+        assert_eq!(vec![('a','b',2), ('b','c',1), ('x','y',4)], edge_values(&g, &forest));
+    }
+}
+
+#[test]
+fn spanning_tree_of_unweighted_graph()
+{
+    let g = Graph::from_vec_undirected(vec![(0,1), (1,2), (2,0), (2,3), (3,0)]);
+    let forest = SpanningTree::prim(&g);
+    assert_eq!(3, forest.total_weight);
+    assert_eq!(3, forest.edges.len());
+}
+
+#[test]
+fn build_graph()
+{
+    let g = example();
+    let forest = SpanningTree::kruskal(&g);
+    let tree = SpanningTree::build_graph(&g, &forest);
+
+    assert!(!tree.is_directed());
+    assert_eq!(g.len(), tree.len());
+    assert_eq!(6, tree.edge_count());
+    for (node, value) in g.nodes() {
+        assert_eq!(*value, tree[node]);
+    }
+    assert_eq!(39, tree.edges().map(|(_, weight)| *weight).sum::<u32>() / 2);
+}
+
+#[test]
+#[should_panic]
+fn spanning_tree_of_directed_graph()
+{
+    let g = WeightedGraph::from([('a','b',1)]);
+    SpanningTree::kruskal(&g);
+}
+
+#[test]
+fn union_find()
+{
+    let mut sets = UnionFind::new(5);
+    assert_eq!(5, sets.len());
+    assert_eq!(5, sets.set_count());
+
+    assert!(sets.union(0, 1));
+    assert!(sets.union(3, 4));
+    assert!(sets.union(1, 4));
+    assert!(!sets.union(0, 3));
+
+    assert_eq!(2, sets.set_count());
+    assert!(sets.same_set(0, 4));
+    assert!(!sets.same_set(2, 4));
+    assert_eq!(4, sets.set_size(3));
+    assert_eq!(1, sets.set_size(2));
+    assert_eq!(sets.find(0), sets.find(3));
+}
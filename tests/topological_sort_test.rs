@@ -0,0 +1,75 @@
+use grapher::graph::{Graph, Node};
+use grapher::topological_sort::{TopologicalSort, CycleError};
+
+fn values<T: Copy>(graph: &Graph<T>, nodes: &[Node]) -> Vec<T> {
+    nodes.iter().map(|node| graph[*node]).collect()
+}
+
+fn assert_is_topological<T>(graph: &Graph<T>, order: &[Node]) {
+    assert_eq!(graph.len(), order.len());
+    let position = |node: Node| order.iter().position(|other| *other == node).unwrap();
+    for edge in graph.edges() {
+        assert!(position(edge.source) < position(edge.target));
+    }
+}
+
+#[test]
+fn sort_dag()
+{
+    let g = Graph::from([("std", "core"), ("app", "std"), ("app", "log"), ("log", "std"), ("test", "app")]);
+    let order = TopologicalSort::sort(&g).unwrap();
+    assert_is_topological(&g, &order);
+}
+
+#[test]
+fn sort_reports_cycle()
+{
+    let g = Graph::from([(0,1), (1,2), (2,3), (3,1), (3,4)]);
+    let error = TopologicalSort::sort(&g).unwrap_err();
+    assert_eq!(vec![1, 2, 3], values(&g, &error.cycle));
+}
+
+#[test]
+fn sort_reports_self_loop()
+{
+    let g = Graph::from([(0,1), (1,1)]);
+    let error = TopologicalSort::sort(&g).unwrap_err();
+    assert_eq!(vec![1], values(&g, &error.cycle));
+    assert_eq!(format!("graph contains a cycle: {:?}", error.cycle), error.to_string());
+}
+
+#[test]
+fn generations()
+{
+    let g = Graph::from([(0,2), (1,2), (2,3), (0,3), (4,5)]);
+    let generations = TopologicalSort::generations(&g).unwrap();
+    let generations = generations.iter().map(|generation| values(&g, generation)).collect::<Vec<_>>();
+    assert_eq!(vec![vec![0, 1, 4], vec![2, 5], vec![3]], generations);
+}
+
+#[test]
+fn generations_report_cycle()
+{
+    let g = Graph::from([(0,1), (1,2), (2,0), (3,0)]);
+    let CycleError { cycle } = TopologicalSort::generations(&g).unwrap_err();
+    assert_eq!(3, cycle.len());
+    for (index, node) in cycle.iter().enumerate() {
+        let next = cycle[(index + 1) % cycle.len()];
+        assert!(g.get_neighbours(*node).any(|edge| edge.target == next));
+    }
+}
+
+#[test]
+fn sort_by_key_is_lexicographically_smallest()
+{
+    let g = Graph::from([(5,11), (7,11), (7,8), (3,8), (3,10), (11,2), (11,9), (11,10), (8,9)]);
+    let order = TopologicalSort::sort_by_key(&g, |node| g[node]).unwrap();
+    assert_eq!(vec![3, 5, 7, 8, 11, 2, 9, 10], values(&g, &order));
+}
+
+#[test]
+fn sort_by_key_reports_cycle()
+{
+    let g = Graph::from([(0,1), (1,0)]);
+    assert!(TopologicalSort::sort_by_key(&g, |node| g[node]).is_err());
+}
@@ -0,0 +1,122 @@
+use grapher::graph::{Graph, Node};
+use grapher::traversal::{Bfs, Dfs, TraversalEvent, Visitor};
+use grapher::weighted_graph::WeightedGraph;
+
+fn n<T: Eq>(graph: &Graph<T>, value: T) -> Node {
+    graph.find_node_with_value(&value).expect("Can't find node")
+}
+
+fn values<T: Copy>(graph: &Graph<T>, nodes: impl Iterator<Item = Node>) -> Vec<T> {
+    nodes.map(|node| graph[node]).collect()
+}
+
+#[test]
+fn dfs_preorder_and_postorder()
+{
+    let g = Graph::from([(0,1), (1,2), (0,3), (3,2)]);
+
+    assert_eq!(vec![0, 1, 2, 3], values(&g, Dfs::new(&g, [n(&g, 0)]).preorder()));
+    assert_eq!(vec![2, 1, 3, 0], values(&g, Dfs::new(&g, [n(&g, 0)]).postorder()));
+}
+
+#[test]
+fn dfs_classifies_edges()
+{
+    let g = Graph::from([(0,1), (1,2), (2,0), (0,2), (3,1)]);
+    let (n0, n1, n2, n3) = (n(&g, 0), n(&g, 1), n(&g, 2), n(&g, 3));
+
+    let edges = Dfs::new(&g, [n0, n3])
+        .filter(|event| !matches!(event, TraversalEvent::Discover { .. } | TraversalEvent::Finish { .. }))
+        .collect::<Vec<_>>();
+
+    assert_eq!(vec![
+        TraversalEvent::TreeEdge(n0, n1),
+        TraversalEvent::TreeEdge(n1, n2),
+        TraversalEvent::BackEdge(n2, n0),
+        TraversalEvent::ForwardEdge(n0, n2),
+        TraversalEvent::CrossEdge(n3, n1)], edges);
+}
+
+#[test]
+fn dfs_times_and_depths()
+{
+    let g = Graph::from([(0,1), (1,2), (5,6)]);
+    let mut dfs = Dfs::new(&g, g.nodes().map(|(node, _)| node));
+    assert_eq!(None, dfs.discovery_time(n(&g, 0)));
+
+    dfs.by_ref().for_each(drop);
+
+    assert_eq!(Some(0), dfs.discovery_time(n(&g, 0)));
+    assert_eq!(Some(2), dfs.discovery_time(n(&g, 2)));
+    assert_eq!(Some(5), dfs.finish_time(n(&g, 0)));
+    assert_eq!(Some(2), dfs.depth(n(&g, 2)));
+    assert_eq!(Some(0), dfs.depth(n(&g, 5)));
+    assert_eq!(Some(1), dfs.depth(n(&g, 6)));
+}
+
+#[test]
+fn bfs_multiple_roots()
+{
+    let g = Graph::from([(0,1), (1,2), (2,3), (4,3)]);
+    let mut bfs = Bfs::new(&g, [n(&g, 0), n(&g, 4)]);
+
+    let order = values(&g, bfs.by_ref().filter_map(|event| match event {
+        TraversalEvent::Discover { node, .. } => Some(node),
+        _ => None
+    }));
+
+    assert_eq!(vec![0, 4, 1, 3, 2], order);
+    assert_eq!(Some(1), bfs.depth(n(&g, 3)));
+    assert_eq!(Some(2), bfs.depth(n(&g, 2)));
+}
+
+#[test]
+fn bfs_over_weighted_graph()
+{
+    let g = WeightedGraph::from([(0,1,5.0), (0,2,1.0), (1,2,1.0)]);
+    let root = g.find_node_with_value(&0).unwrap();
+    let order = Bfs::new(&g, [root]).preorder().map(|node| g[node]).collect::<Vec<_>>();
+    assert_eq!(vec![0, 1, 2], order);
+}
+
+#[derive(Default)]
+struct Counter {
+    discovered: usize,
+    finished: usize,
+    tree_edges: usize,
+    non_tree_edges: usize,
+    max_depth: usize
+}
+
+impl Visitor<Node> for Counter {
+    fn discover(&mut self, _node: Node, _time: usize, depth: usize) {
+        self.discovered += 1;
+        self.max_depth = self.max_depth.max(depth);
+    }
+
+    fn finish(&mut self, _node: Node, _time: usize) {
+        self.finished += 1;
+    }
+
+    fn tree_edge(&mut self, _source: Node, _target: Node) {
+        self.tree_edges += 1;
+    }
+
+    fn non_tree_edge(&mut self, _source: Node, _target: Node) {
+        self.non_tree_edges += 1;
+    }
+}
+
+#[test]
+fn bfs_visitor()
+{
+    let g = Graph::from_vec_undirected(vec![(0,1), (1,2), (2,0), (2,3)]);
+    let mut counter = Counter::default();
+    Bfs::new(&g, [n(&g, 0)]).visit(&mut counter);
+
+    assert_eq!(4, counter.discovered);
+    assert_eq!(4, counter.finished);
+    assert_eq!(3, counter.tree_edges);
+    assert_eq!(5, counter.non_tree_edges);
+    assert_eq!(2, counter.max_depth);
+}